@@ -0,0 +1,189 @@
+use std::cell::RefCell;
+use std::rc::*;
+
+use wasmuri_container::*;
+
+pub trait Focusable {
+
+    fn is_focused(&self) -> bool;
+
+    fn set_focused(&mut self, focused: bool);
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn set_focus_handle(&mut self, _handle: Option<FocusHandle>) {}
+}
+
+fn same_focusable(a: &Rc<RefCell<dyn Focusable>>, b: &Rc<RefCell<dyn Focusable>>) -> bool {
+    Rc::as_ptr(a) as *const u8 == Rc::as_ptr(b) as *const u8
+}
+
+#[derive(Clone)]
+pub struct FocusHandle {
+
+    behavior: Weak<RefCell<FocusBehavior>>,
+    focusable: Weak<RefCell<dyn Focusable>>
+}
+
+impl FocusHandle {
+
+    pub fn new(behavior: &Rc<RefCell<FocusBehavior>>, focusable: &Rc<RefCell<dyn Focusable>>) -> FocusHandle {
+        FocusHandle {
+            behavior: Rc::downgrade(behavior),
+            focusable: Rc::downgrade(focusable)
+        }
+    }
+
+    // The focusable is usually borrowed while it claims the focus, so it must mark itself as focused
+    pub fn claim_focus(&self) {
+        if let Some(behavior) = self.behavior.upgrade() {
+            behavior.borrow_mut().blur_others(&self.focusable);
+        }
+    }
+}
+
+pub struct FocusBehavior {
+
+    agent: Option<Weak<RefCell<ComponentAgent>>>,
+    focusables: Vec<Rc<RefCell<dyn Focusable>>>
+}
+
+impl Default for FocusBehavior {
+
+    fn default() -> FocusBehavior {
+        Self::new()
+    }
+}
+
+impl FocusBehavior {
+
+    pub fn new() -> FocusBehavior {
+        FocusBehavior {
+            agent: None,
+            focusables: Vec::new()
+        }
+    }
+
+    pub fn celled() -> Rc<RefCell<FocusBehavior>> {
+        Rc::new(RefCell::new(Self::new()))
+    }
+
+    pub fn add_focusable(&mut self, focusable: Rc<RefCell<dyn Focusable>>) {
+        self.focusables.push(focusable);
+    }
+
+    pub fn remove_focusable(&mut self, focusable: &Rc<RefCell<dyn Focusable>>) {
+        self.focusables.retain(|candidate| !same_focusable(candidate, focusable));
+    }
+
+    pub fn get_focused_index(&self) -> Option<usize> {
+        self.focusables.iter().position(|focusable| focusable.borrow().is_focused())
+    }
+
+    pub fn get_focused(&self) -> Option<Rc<RefCell<dyn Focusable>>> {
+        self.get_focused_index().map(|index| Rc::clone(&self.focusables[index]))
+    }
+
+    pub fn focus(&mut self, target: &Rc<RefCell<dyn Focusable>>) {
+        match self.focusables.iter().position(|focusable| same_focusable(focusable, target)) {
+            Some(index) => self.focus_index(index),
+            None => {
+                self.blur();
+                target.borrow_mut().set_focused(true);
+            }
+        }
+    }
+
+    pub fn focus_index(&mut self, index: usize) {
+
+        // Blur the others first, so that there is never more than 1 focused component
+        for (other_index, focusable) in self.focusables.iter().enumerate() {
+            if other_index != index && focusable.borrow().is_focused() {
+                focusable.borrow_mut().set_focused(false);
+            }
+        }
+        let mut target = self.focusables[index].borrow_mut();
+        if !target.is_focused() {
+            target.set_focused(true);
+        }
+    }
+
+    pub fn blur_others(&mut self, keep: &Weak<RefCell<dyn Focusable>>) {
+        for focusable in &self.focusables {
+            if Rc::as_ptr(focusable) as *const u8 != keep.as_ptr() as *const u8 && focusable.borrow().is_focused() {
+                focusable.borrow_mut().set_focused(false);
+            }
+        }
+    }
+
+    pub fn blur(&mut self) {
+        for focusable in &self.focusables {
+            if focusable.borrow().is_focused() {
+                focusable.borrow_mut().set_focused(false);
+            }
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focus_step(true);
+    }
+
+    pub fn focus_previous(&mut self) {
+        self.focus_step(false);
+    }
+
+    fn focus_step(&mut self, forward: bool) {
+        let amount = self.focusables.len();
+        if amount == 0 {
+            return;
+        }
+
+        let step = match forward {
+            true => 1,
+            false => amount - 1
+        };
+        let mut index = match (self.get_focused_index(), forward) {
+            (Some(index), _) => index,
+            (None, true) => amount - 1,
+            (None, false) => 0
+        };
+
+        // Skip the components that can't be focused right now, like disabled ones
+        for _ in 0..amount {
+            index = (index + step) % amount;
+            if self.focusables[index].borrow().can_focus() {
+                self.focus_index(index);
+                return;
+            }
+        }
+    }
+}
+
+impl ComponentBehavior for FocusBehavior {
+
+    fn attach(&mut self, agent: &mut dyn LayerAgent){
+        agent.make_key_down_listener(5);
+    }
+
+    fn set_agent(&mut self, agent: Weak<RefCell<ComponentAgent>>){
+        self.agent = Some(agent);
+    }
+
+    fn get_agent(&self) -> &Weak<RefCell<ComponentAgent>> {
+        self.agent.as_ref().expect("Agent should have been set by now")
+    }
+
+    fn key_down(&mut self, params: &mut KeyDownParams) -> bool {
+        if params.keys.get_key() == "Tab" && !params.keys.is_control_down() && !self.focusables.is_empty() {
+            match params.keys.is_shift_down() {
+                true => self.focus_previous(),
+                false => self.focus_next()
+            };
+            true
+        } else {
+            false
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+mod focus;
+
+pub use focus::*;
\ No newline at end of file
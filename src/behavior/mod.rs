@@ -1,5 +1,7 @@
 mod render;
 mod mouse;
+mod key;
 
 pub use render::*;
-pub use mouse::*;
\ No newline at end of file
+pub use mouse::*;
+pub use key::*;
\ No newline at end of file
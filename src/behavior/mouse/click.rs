@@ -1,4 +1,6 @@
-use crate::behavior::TextRenderController;
+use crate::{ComponentError, upgrade_agent};
+use crate::behavior::{FocusHandle, TextRenderController};
+use crate::time::current_time;
 
 use std::cell::RefCell;
 use std::rc::*;
@@ -6,37 +8,299 @@ use std::rc::*;
 use std::convert::AsMut;
 
 use wasmuri_container::*;
+use wasmuri_core::*;
 
-pub struct ClickActionBehavior {
+pub enum ClickSource<'a> {
 
-    on_click: Box<dyn FnMut(&mut ComponentAgent, &mut dyn TextRenderController, &mut MouseClickParams)>,
+    Mouse(&'a mut MouseClickParams),
+    Keyboard(&'a str)
+}
+
+pub type RegionClickAction<T> = Box<dyn FnMut(&mut ComponentAgent, &mut T, &mut ClickSource)>;
+
+pub type ClickAction = RegionClickAction<dyn TextRenderController>;
+
+pub trait ClickTarget {
+
+    fn get_max_region(&self) -> Region;
+
+    fn get_current_region(&self) -> Region {
+        self.get_max_region()
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    fn is_focused(&self) -> bool {
+        false
+    }
+
+    fn set_focused(&mut self, _focused: bool) {}
+
+    fn set_pressed(&mut self, _pressed: bool) {}
+}
+
+// Lets text components keep using their render controller as the click target
+impl ClickTarget for dyn TextRenderController {
+
+    fn get_max_region(&self) -> Region {
+        TextRenderController::get_max_region(self)
+    }
+
+    fn get_current_region(&self) -> Region {
+        TextRenderController::get_current_region(self)
+    }
+
+    fn is_enabled(&self) -> bool {
+        TextRenderController::is_enabled(self)
+    }
+
+    fn is_focused(&self) -> bool {
+        TextRenderController::is_focused(self)
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        TextRenderController::set_focused(self, focused);
+    }
+
+    fn set_pressed(&mut self, pressed: bool) {
+        TextRenderController::set_pressed(self, pressed);
+    }
+}
+
+impl ClickTarget for Region {
+
+    fn get_max_region(&self) -> Region {
+        *self
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MouseButton {
+
+    Primary,
+    Middle,
+    Secondary
+}
+
+fn mouse_button(index: i16) -> Option<MouseButton> {
+    match index {
+        0 => Some(MouseButton::Primary),
+        1 => Some(MouseButton::Middle),
+        2 => Some(MouseButton::Secondary),
+        _ => None
+    }
+}
+
+// When the new click space is taken, the old one will be claimed again
+pub(crate) fn reclaim_click_space(agent: &mut ComponentAgent, old_region: Region, new_region: Region, owner: &'static str) -> Result<(), ComponentError> {
+    agent.release_mouse_click_space(old_region);
+    if agent.claim_mouse_click_space(new_region).is_err() {
+        agent.claim_mouse_click_space(old_region).expect("Should be able to claim the released click space again");
+        return Err(ComponentError::ClickSpaceTaken(owner));
+    }
+    Ok(())
+}
+
+pub const DEFAULT_DOUBLE_CLICK_TIME: f64 = 500.0;
+
+// Like the mouse positions of the events, this is in the float coordinates of regions rather than in pixels
+pub const DEFAULT_DOUBLE_CLICK_DISTANCE: f32 = 0.01;
+
+pub struct RegionClickBehavior<T: ClickTarget + ?Sized> {
+
+    on_click: RegionClickAction<T>,
+    on_middle_click: Option<RegionClickAction<T>>,
+    on_secondary_click: Option<RegionClickAction<T>>,
+    on_double_click: Option<RegionClickAction<T>>,
 
     agent: Option<Weak<RefCell<ComponentAgent>>>,
-    render_controller: Rc<RefCell<dyn TextRenderController>>
+    target: Rc<RefCell<T>>,
+    attached: bool,
+    claimed_region: Option<Region>,
+    visible: bool,
+    focused: bool,
+    key_listening: bool,
+    focus_handle: Option<FocusHandle>,
+    attach_error: Option<ComponentError>,
+
+    pressed: [bool; 3],
+    space_pressed: bool,
+
+    double_click_time: f64,
+    double_click_distance: f32,
+    last_click: Option<(f64, Vector2f)>
 }
 
-impl ClickActionBehavior {
+pub type ClickActionBehavior = RegionClickBehavior<dyn TextRenderController>;
 
-    pub fn new(on_click: Box<dyn FnMut(&mut ComponentAgent, &mut dyn TextRenderController, &mut MouseClickParams)>,
-    render_controller: Rc<RefCell<dyn TextRenderController>>) -> ClickActionBehavior {
-        ClickActionBehavior {
+impl<T: ClickTarget + ?Sized> RegionClickBehavior<T> {
+
+    pub fn new(on_click: RegionClickAction<T>, target: Rc<RefCell<T>>) -> RegionClickBehavior<T> {
+        RegionClickBehavior {
             on_click,
+            on_middle_click: None,
+            on_secondary_click: None,
+            on_double_click: None,
 
             agent: None,
-            render_controller
+            target,
+            attached: false,
+            claimed_region: None,
+            visible: true,
+            focused: false,
+            key_listening: false,
+            focus_handle: None,
+            attach_error: None,
+
+            pressed: [false; 3],
+            space_pressed: false,
+
+            double_click_time: DEFAULT_DOUBLE_CLICK_TIME,
+            double_click_distance: DEFAULT_DOUBLE_CLICK_DISTANCE,
+            last_click: None
         }
     }
 
-    pub fn celled(on_click: Box<dyn FnMut(&mut ComponentAgent, &mut dyn TextRenderController, &mut MouseClickParams)>,
-    render_controller: Rc<RefCell<dyn TextRenderController>>) -> Rc<RefCell<ClickActionBehavior>> {
-        Rc::new(RefCell::new(Self::new(on_click, render_controller)))
+    pub fn celled(on_click: RegionClickAction<T>, target: Rc<RefCell<T>>) -> Rc<RefCell<RegionClickBehavior<T>>> {
+        Rc::new(RefCell::new(Self::new(on_click, target)))
+    }
+
+    pub fn set_on_middle_click(&mut self, on_middle_click: Option<RegionClickAction<T>>){
+        self.on_middle_click = on_middle_click;
+    }
+
+    pub fn set_on_secondary_click(&mut self, on_secondary_click: Option<RegionClickAction<T>>){
+        self.on_secondary_click = on_secondary_click;
+    }
+
+    // The normal click action still fires for both clicks, and the double click action fires right after the second one
+    pub fn set_on_double_click(&mut self, on_double_click: Option<RegionClickAction<T>>){
+        self.on_double_click = on_double_click;
+    }
+
+    // The distance is in the same coordinate space as the mouse positions of the click events
+    pub fn set_double_click_threshold(&mut self, max_time: f64, max_distance: f32){
+        self.double_click_time = max_time;
+        self.double_click_distance = max_distance;
+    }
+
+    // Should be called after the max region of the target has changed
+    pub fn update_click_space(&mut self) -> Result<(), ComponentError> {
+        if let Some(old_region) = self.claimed_region {
+            let new_region = self.target.borrow().get_max_region();
+            let agent_cell = upgrade_agent(&self.agent, "RegionClickBehavior")?;
+            reclaim_click_space(&mut agent_cell.borrow_mut(), old_region, new_region, "RegionClickBehavior")?;
+            self.claimed_region = Some(new_region);
+            self.pressed = [false; 3];
+            self.last_click = None;
+        }
+        Ok(())
+    }
+
+    pub fn set_visible(&mut self, visible: bool) -> Result<(), ComponentError> {
+        if self.visible == visible {
+            return Ok(());
+        }
+        self.pressed = [false; 3];
+        self.space_pressed = false;
+        self.last_click = None;
+
+        // Before attaching, there is nothing to give up and attach will claim the spaces
+        if self.attached {
+            let agent_cell = upgrade_agent(&self.agent, "RegionClickBehavior")?;
+            let mut agent = agent_cell.borrow_mut();
+            if visible {
+                let region = self.target.borrow().get_max_region();
+                agent.claim_mouse_click_space(region).map_err(|_| ComponentError::ClickSpaceTaken("RegionClickBehavior"))?;
+                self.claimed_region = Some(region);
+            } else if let Some(old_region) = self.claimed_region.take() {
+                agent.release_mouse_click_space(old_region);
+            }
+            self.visible = visible;
+            self.update_key_listeners(&mut agent);
+        }
+        self.visible = visible;
+        Ok(())
+    }
+
+    // Only the focused target can be clicked with the keyboard, so the others don't need to listen to keys
+    pub fn set_focused(&mut self, focused: bool) {
+        if !focused && self.space_pressed {
+            self.space_pressed = false;
+            self.target.borrow_mut().set_pressed(false);
+        }
+        self.focused = focused;
+
+        // A removed component doesn't have key listeners to update
+        if let Some(agent_cell) = upgrade_agent(&self.agent, "RegionClickBehavior").ok().filter(|_| self.attached) {
+            self.update_key_listeners(&mut agent_cell.borrow_mut());
+        }
+    }
+
+    pub fn set_focus_handle(&mut self, handle: Option<FocusHandle>) {
+        self.focus_handle = handle;
+    }
+
+    fn update_key_listeners(&mut self, agent: &mut ComponentAgent) {
+        let should_listen = self.visible && self.focused;
+        if self.key_listening != should_listen {
+            if should_listen {
+                agent.make_key_down_listener(10);
+                agent.make_key_up_listener(10);
+            } else {
+                agent.remove_key_down_listener();
+                agent.remove_key_up_listener();
+            }
+            self.key_listening = should_listen;
+        }
+    }
+
+    pub fn try_attach(&mut self, agent: &mut dyn LayerAgent) -> Result<(), ComponentError> {
+        if self.visible {
+            let region = self.target.borrow().get_max_region();
+            agent.claim_mouse_click_space(region).map_err(|_| ComponentError::ClickSpaceTaken("RegionClickBehavior"))?;
+            self.claimed_region = Some(region);
+            if self.focused {
+                agent.make_key_down_listener(10);
+                agent.make_key_up_listener(10);
+                self.key_listening = true;
+            }
+        }
+        self.attached = true;
+        Ok(())
+    }
+
+    pub fn take_attach_error(&mut self) -> Option<ComponentError> {
+        self.attach_error.take()
+    }
+
+    fn is_double_click(&mut self, mouse_pos: Vector2f) -> bool {
+        let click_time = current_time();
+        let is_double = match self.last_click {
+            Some((last_time, last_pos)) => {
+                let dx = mouse_pos.get_x() - last_pos.get_x();
+                let dy = mouse_pos.get_y() - last_pos.get_y();
+                click_time - last_time <= self.double_click_time && dx * dx + dy * dy <= self.double_click_distance * self.double_click_distance
+            }, None => false
+        };
+
+        // A third click should start a new pair rather than complete another double click
+        self.last_click = match is_double {
+            true => None,
+            false => Some((click_time, mouse_pos))
+        };
+        is_double
     }
 }
 
-impl ComponentBehavior for ClickActionBehavior {
+impl<T: ClickTarget + ?Sized> ComponentBehavior for RegionClickBehavior<T> {
 
     fn attach(&mut self, agent: &mut dyn LayerAgent){
-        agent.claim_mouse_click_space(self.render_controller.borrow().get_max_region()).expect("Should have click space for ClickActionBehavior");
+        if let Err(error) = self.try_attach(agent) {
+            self.attach_error = Some(error);
+        }
     }
 
     fn set_agent(&mut self, agent: Weak<RefCell<ComponentAgent>>){
@@ -47,13 +311,115 @@ impl ComponentBehavior for ClickActionBehavior {
         self.agent.as_ref().expect("Agent should have been set by now")
     }
 
+    fn mouse_down_inside(&mut self, params: &mut MouseDownParams) {
+        let button = match mouse_button(params.down.get_button()) {
+            Some(button) => button,
+            None => return
+        };
+        let mut target = self.target.borrow_mut();
+        if target.is_enabled() && target.get_current_region().is_float_inside(params.mouse_pos) {
+            self.pressed[button as usize] = true;
+            if button == MouseButton::Primary {
+                target.set_pressed(true);
+            }
+        }
+    }
+
+    fn mouse_up(&mut self, params: &mut MouseUpParams) {
+        let button = match mouse_button(params.up.get_button()) {
+            Some(button) => button,
+            None => return
+        };
+        if self.pressed[button as usize] {
+            let mut target = self.target.borrow_mut();
+            if button == MouseButton::Primary {
+                target.set_pressed(false);
+            }
+
+            // Like native buttons, only fire when the mouse is released inside the button
+            self.pressed[button as usize] = target.get_current_region().is_float_inside(params.mouse_pos);
+        }
+    }
+
     fn mouse_click_inside(&mut self, params: &mut MouseClickParams) {
-        if params.click.get_button() == 0 {
-            let mut render_controller = self.render_controller.borrow_mut();
-            if render_controller.get_current_region().is_float_inside(params.mouse_pos) {
-                let agent_cell = self.get_agent().upgrade().expect("Agent shouldn't have been dropped");
-                self.on_click.as_mut()(&mut agent_cell.borrow_mut(), &mut *render_controller, params);
+        let button = match mouse_button(params.click.get_button()) {
+            Some(button) => button,
+            None => return
+        };
+        if !self.pressed[button as usize] {
+            return;
+        }
+        self.pressed[button as usize] = false;
+
+        let target_cell = Rc::clone(&self.target);
+        let mut target = target_cell.borrow_mut();
+        if target.is_enabled() && target.get_current_region().is_float_inside(params.mouse_pos) {
+
+            // Clicking focuses the target, like clicking an edit field does
+            if let (MouseButton::Primary, false, Some(focus_handle)) = (button, self.focused, &self.focus_handle) {
+                focus_handle.claim_focus();
+                target.set_focused(true);
+                self.set_focused(true);
             }
+            let agent_cell = self.get_agent().upgrade().expect("Agent shouldn't have been dropped");
+            let mut agent = agent_cell.borrow_mut();
+            match button {
+                MouseButton::Primary => {
+                    let is_double = self.on_double_click.is_some() && self.is_double_click(params.mouse_pos);
+                    self.on_click.as_mut()(&mut agent, &mut *target, &mut ClickSource::Mouse(params));
+                    if is_double {
+                        let on_double_click = self.on_double_click.as_mut().expect("Double click action was checked");
+                        on_double_click(&mut agent, &mut *target, &mut ClickSource::Mouse(params));
+                    }
+                },
+                MouseButton::Middle => if let Some(on_middle_click) = self.on_middle_click.as_mut() {
+                    on_middle_click(&mut agent, &mut *target, &mut ClickSource::Mouse(params));
+                },
+                MouseButton::Secondary => if let Some(on_secondary_click) = self.on_secondary_click.as_mut() {
+                    on_secondary_click(&mut agent, &mut *target, &mut ClickSource::Mouse(params));
+                }
+            }
+        }
+    }
+
+    fn key_down(&mut self, params: &mut KeyDownParams) -> bool {
+        let key = params.keys.get_key();
+        let mut target = self.target.borrow_mut();
+        if !target.is_enabled() || !target.is_focused() {
+            return false;
+        }
+        match key {
+            "Enter" => {
+                let agent_cell = self.get_agent().upgrade().expect("Agent shouldn't have been dropped");
+                self.on_click.as_mut()(&mut agent_cell.borrow_mut(), &mut *target, &mut ClickSource::Keyboard(key));
+                true
+            },
+
+            // Like native buttons, Space only clicks when it is released, which also ignores the repeated key downs
+            " " | "Spacebar" => {
+                if !self.space_pressed {
+                    self.space_pressed = true;
+                    target.set_pressed(true);
+                }
+                true
+            },
+            _ => false
+        }
+    }
+
+    fn key_up(&mut self, params: &mut KeyUpParams) -> bool {
+        let key = params.keys.get_key();
+        if !self.space_pressed || (key != " " && key != "Spacebar") {
+            return false;
+        }
+        self.space_pressed = false;
+
+        let mut target = self.target.borrow_mut();
+        target.set_pressed(false);
+        if target.is_enabled() && target.is_focused() {
+            let agent_cell = self.get_agent().upgrade().expect("Agent shouldn't have been dropped");
+            self.on_click.as_mut()(&mut agent_cell.borrow_mut(), &mut *target, &mut ClickSource::Keyboard(key));
         }
+        true
     }
 }
\ No newline at end of file
@@ -0,0 +1,679 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use wasmuri_container::*;
+use wasmuri_core::*;
+use wasmuri_text::*;
+
+use super::*;
+
+use crate::{ComponentError, upgrade_agent};
+use crate::behavior::{FocusHandle, Focusable, reclaim_click_space};
+use crate::time::current_time;
+
+#[derive(Clone,Copy)]
+struct VisualRow {
+
+    line: usize,
+    start: usize,
+    end: usize
+}
+
+pub struct TextAreaRenderController {
+
+    max_region: Region,
+    line_height: f32,
+    agent: Option<Weak<RefCell<ComponentAgent>>>,
+    font: Rc<Font>,
+
+    base_colors: TextColors,
+    hover_colors: TextColors,
+    active_colors: TextColors,
+    render_opacity: Option<RenderOpacity>,
+    attach_error: Option<ComponentError>,
+
+    visible: bool,
+    active: bool,
+    mouse_over: bool,
+    last_mouse_pos: Option<Vector2f>,
+    lines: Vec<String>,
+    line_widths: Vec<Vec<f32>>,
+    rows: Vec<VisualRow>,
+    first_visible_row: usize,
+
+    caret_line: usize,
+    caret_column: usize,
+    caret_time: f64,
+
+    submit_on_control_enter: bool,
+    on_change: Option<EditChangeAction>,
+    on_submit: Option<EditSubmitAction>,
+
+    focus_handle: Option<FocusHandle>
+}
+
+fn split_lines(text: &str) -> Vec<String> {
+    text.split('\n').map(|line| line.trim_end_matches('\r').to_string()).collect()
+}
+
+impl TextAreaRenderController {
+
+    pub fn new(text: &str, font: &Rc<Font>, max_region: Region, line_height: f32, base_colors: TextColors,
+            hover_colors: TextColors, active_colors: TextColors) -> TextAreaRenderController {
+        let lines = split_lines(text);
+        let caret_line = lines.len() - 1;
+        let caret_column = count_graphemes(&lines[caret_line]);
+        let mut instance = TextAreaRenderController {
+            max_region,
+            line_height,
+            agent: None,
+            font: Rc::clone(font),
+
+            base_colors,
+            hover_colors,
+            active_colors,
+            render_opacity: None,
+            attach_error: None,
+
+            visible: true,
+            active: false,
+            mouse_over: false,
+            last_mouse_pos: None,
+            lines,
+            line_widths: Vec::new(),
+            rows: Vec::new(),
+            first_visible_row: 0,
+
+            caret_line,
+            caret_column,
+            caret_time: 0.0,
+
+            submit_on_control_enter: false,
+            on_change: None,
+            on_submit: None,
+
+            focus_handle: None
+        };
+        instance.measure_all_lines();
+        instance
+    }
+
+    pub fn celled(text: &str, font: &Rc<Font>, max_region: Region, line_height: f32, base_colors: TextColors,
+            hover_colors: TextColors, active_colors: TextColors) -> Rc<RefCell<TextAreaRenderController>> {
+        Rc::new(RefCell::new(Self::new(text, font, max_region, line_height, base_colors, hover_colors, active_colors)))
+    }
+
+    pub fn simple(text: &str, font: &Rc<Font>, max_region: Region, line_height: f32, colors: TextColors) -> TextAreaRenderController {
+        Self::new(text, font, max_region, line_height, colors, to_hover_colors(colors), to_active_colors(colors))
+    }
+
+    pub fn simple_celled(text: &str, font: &Rc<Font>, max_region: Region, line_height: f32, colors: TextColors) -> Rc<RefCell<TextAreaRenderController>> {
+        Rc::new(RefCell::new(Self::simple(text, font, max_region, line_height, colors)))
+    }
+
+    pub fn tuple(text: &str, font: &Rc<Font>, max_region: Region, line_height: f32, base_colors: TextColors,
+            hover_colors: TextColors, active_colors: TextColors) -> (Rc<RefCell<dyn ComponentBehavior>>, Rc<RefCell<TextAreaRenderController>>) {
+        let instance = Self::celled(text, font, max_region, line_height, base_colors, hover_colors, active_colors);
+        (Rc::clone(&instance) as Rc<RefCell<dyn ComponentBehavior>>, instance)
+    }
+
+    pub fn simple_tuple(text: &str, font: &Rc<Font>, max_region: Region, line_height: f32, colors: TextColors)
+            -> (Rc<RefCell<dyn ComponentBehavior>>, Rc<RefCell<TextAreaRenderController>>) {
+        let instance = Self::simple_celled(text, font, max_region, line_height, colors);
+        (Rc::clone(&instance) as Rc<RefCell<dyn ComponentBehavior>>, instance)
+    }
+
+    pub fn set_base_colors(&mut self, new_colors: TextColors){
+        self.base_colors = new_colors;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_hover_colors(&mut self, new_colors: TextColors){
+        self.hover_colors = new_colors;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_active_colors(&mut self, new_colors: TextColors){
+        self.active_colors = new_colors;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_colors(&mut self, new_colors: TextColors){
+        self.base_colors = new_colors;
+        self.hover_colors = to_hover_colors(new_colors);
+        self.active_colors = to_active_colors(new_colors);
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn set_active(&mut self, new_active: bool) {
+        self.active = new_active && self.visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) -> Result<(), ComponentError> {
+        if self.visible == visible {
+            return Ok(());
+        }
+
+        // Only attached controllers have claims to give up or restore
+        if self.render_opacity.is_some() {
+            let all_colors = self.get_all_colors();
+            let agent_cell = upgrade_agent(&self.agent, "TextAreaRenderController")?;
+            let mut agent = agent_cell.borrow_mut();
+            set_render_space_visible(&mut agent, self.max_region, visible, all_colors, &mut self.render_opacity, "TextAreaRenderController")?;
+            if visible {
+                if agent.claim_mouse_click_space(self.max_region).is_err() {
+                    agent.release_render_space(self.max_region);
+                    return Err(ComponentError::ClickSpaceTaken("TextAreaRenderController"));
+                }
+                agent.claim_mouse_in_out_space(self.max_region);
+                agent.make_key_down_listener(10);
+                agent.make_paste_listener(50);
+            } else {
+                agent.release_mouse_in_out_space(self.max_region);
+                agent.remove_key_down_listener();
+                agent.release_mouse_click_space(self.max_region);
+                agent.remove_paste_listener();
+            }
+            agent.request_render();
+        }
+        self.visible = visible;
+        if !visible {
+            self.active = false;
+            self.mouse_over = false;
+        }
+        if visible {
+            self.update_mouse_over();
+        }
+        Ok(())
+    }
+
+    pub fn get_max_region(&self) -> Region {
+        self.max_region
+    }
+
+    pub fn set_region(&mut self, new_region: Region) -> Result<(), ComponentError> {
+        let old_region = self.max_region;
+
+        // Only attached and visible controllers have claimed spaces that need to be moved
+        if let Some(render_opacity) = self.render_opacity.filter(|_| self.visible) {
+            let agent_cell = upgrade_agent(&self.agent, "TextAreaRenderController")?;
+            let mut agent = agent_cell.borrow_mut();
+            reclaim_render_space(&mut agent, old_region, new_region, render_opacity, "TextAreaRenderController")?;
+            if let Err(error) = reclaim_click_space(&mut agent, old_region, new_region, "TextAreaRenderController") {
+                reclaim_render_space(&mut agent, new_region, old_region, render_opacity, "TextAreaRenderController")
+                        .expect("Should be able to claim the released render space again");
+                return Err(error);
+            }
+            agent.release_mouse_in_out_space(old_region);
+            agent.claim_mouse_in_out_space(new_region);
+            agent.request_render();
+        }
+        self.max_region = new_region;
+        self.rows = self.compute_rows();
+        self.update_mouse_over();
+        Ok(())
+    }
+
+    pub fn get_lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn get_current_text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn set_text(&mut self, new_text: &str) {
+        self.lines = split_lines(new_text);
+        self.caret_line = self.lines.len() - 1;
+        self.caret_column = count_graphemes(&self.lines[self.caret_line]);
+        self.measure_all_lines();
+        self.request_render_if_attached();
+    }
+
+    pub fn get_caret(&self) -> (usize, usize) {
+        (self.caret_line, self.caret_column)
+    }
+
+    pub fn set_submit_on_control_enter(&mut self, submit_on_control_enter: bool) {
+        self.submit_on_control_enter = submit_on_control_enter;
+    }
+
+    pub fn set_on_change(&mut self, on_change: Option<EditChangeAction>) {
+        self.on_change = on_change;
+    }
+
+    pub fn set_on_submit(&mut self, on_submit: Option<EditSubmitAction>) {
+        self.on_submit = on_submit;
+    }
+
+    fn agent(&self) -> Rc<RefCell<ComponentAgent>> {
+        self.agent.as_ref().expect("Component agent should have been set by now").upgrade().expect("Component agent should not have been dropped")
+    }
+
+    fn get_all_colors(&self) -> Vec<TextColors> {
+        vec![self.base_colors, self.hover_colors, self.active_colors]
+    }
+
+    fn update_render_opacity(&mut self){
+        if let Some(agent_cell) = self.agent.as_ref().filter(|_| self.visible).and_then(|agent| agent.upgrade()) {
+            let all_colors = self.get_all_colors();
+            update_render_opacity(&mut agent_cell.borrow_mut(), self.max_region, &mut self.render_opacity, all_colors, "TextAreaRenderController");
+        }
+    }
+
+    pub fn try_attach(&mut self, agent: &mut dyn LayerAgent) -> Result<(), ComponentError> {
+        let render_opacity = determine_render_opacity(self.get_all_colors());
+
+        // Hidden areas claim their spaces when they are shown
+        if self.visible {
+            agent.claim_render_space(self.max_region, RenderTrigger::Request, render_opacity,
+                    RenderPhase::Text).map_err(|_| ComponentError::RenderSpaceTaken("TextAreaRenderController"))?;
+            if agent.claim_mouse_click_space(self.max_region).is_err() {
+
+                // Don't leave the area half attached
+                agent.release_render_space(self.max_region);
+                return Err(ComponentError::ClickSpaceTaken("TextAreaRenderController"));
+            }
+            agent.claim_mouse_in_out_space(self.max_region);
+            agent.make_key_down_listener(10);
+            agent.make_paste_listener(50);
+        }
+        self.render_opacity = Some(render_opacity);
+        self.last_mouse_pos = agent.get_mouse_position();
+        self.mouse_over = self.visible && self.last_mouse_pos.is_some() && self.max_region.is_float_inside(self.last_mouse_pos.unwrap());
+        Ok(())
+    }
+
+    pub fn take_attach_error(&mut self) -> Option<ComponentError> {
+        self.attach_error.take()
+    }
+
+    fn request_render_if_attached(&self){
+        if let Some(agent) = self.agent.as_ref().and_then(|agent| agent.upgrade()) {
+            agent.borrow_mut().request_render();
+        }
+    }
+
+    fn update_mouse_over(&mut self){
+        let new_mouse_over = self.visible && self.last_mouse_pos.is_some() && self.max_region.is_float_inside(self.last_mouse_pos.unwrap());
+
+        if !self.active && self.mouse_over != new_mouse_over {
+            self.request_render_if_attached();
+        }
+        self.mouse_over = new_mouse_over;
+    }
+
+    fn get_column_width(&self, line: usize, start: usize, end: usize) -> f32 {
+        let widths = &self.line_widths[line];
+        (widths[end] - widths[start]) * self.line_height
+    }
+
+    // Measuring creates a text model per grapheme, so only the lines that changed are measured again
+    fn update_line_widths(&mut self, first_line: usize, old_line_count: usize, new_line_count: usize){
+        let new_widths: Vec<Vec<f32>> = self.lines[first_line..first_line + new_line_count].iter()
+                .map(|line| measure_prefix_widths(&self.font, line)).collect();
+        self.line_widths.splice(first_line..first_line + old_line_count, new_widths);
+        self.rows = self.compute_rows();
+    }
+
+    fn measure_all_lines(&mut self){
+        self.update_line_widths(0, self.line_widths.len(), self.lines.len());
+    }
+
+    fn compute_rows(&self) -> Vec<VisualRow> {
+        let max_width = self.max_region.get_float_width();
+        let mut rows = Vec::new();
+        for (line_index, line) in self.lines.iter().enumerate() {
+            let length = self.line_widths[line_index].len() - 1;
+            let word_starts: Vec<usize> = UnicodeSegmentation::split_word_bound_indices(line.as_str())
+                    .map(|(offset, _)| count_graphemes(&line[..offset])).collect();
+
+            let mut row_start = 0;
+            loop {
+                let mut row_end = row_start;
+                while row_end < length && self.get_column_width(line_index, row_start, row_end + 1) <= max_width {
+                    row_end += 1;
+                }
+
+                if row_end == length {
+                    rows.push(VisualRow { line: line_index, start: row_start, end: length });
+                    break;
+                }
+
+                if row_end == row_start {
+
+                    // A single grapheme that is wider than the region gets a row on its own
+                    row_end += 1;
+                } else if let Some(word_start) = word_starts.iter().rev().find(|word_start| **word_start > row_start && **word_start <= row_end) {
+
+                    // Prefer to wrap at the start of a word
+                    row_end = *word_start;
+                }
+                rows.push(VisualRow { line: line_index, start: row_start, end: row_end });
+                row_start = row_end;
+            }
+        }
+        rows
+    }
+
+    fn get_visible_row_count(&self) -> usize {
+        ((self.max_region.get_float_height() / self.line_height) as usize).max(1)
+    }
+
+    fn get_caret_row(&self) -> usize {
+        for (index, row) in self.rows.iter().enumerate() {
+            let last_row_of_line = index + 1 == self.rows.len() || self.rows[index + 1].line != row.line;
+            if row.line == self.caret_line && self.caret_column >= row.start && (self.caret_column < row.end || last_row_of_line) {
+                return index;
+            }
+        }
+        0
+    }
+
+    fn get_column_at(&self, row: VisualRow, x: f32) -> usize {
+        let relative_x = x - self.max_region.get_float_min_x();
+        get_closest_boundary(relative_x, row.start, row.end, |column| self.get_column_width(row.line, row.start, column))
+    }
+
+    fn get_row_y(&self, row_index: usize) -> f32 {
+        let max_y = self.max_region.get_float_min_y() + self.max_region.get_float_height();
+        max_y - (row_index - self.first_visible_row + 1) as f32 * self.line_height
+    }
+
+    fn update_first_visible_row(&mut self) {
+        let caret_row = self.get_caret_row();
+        let visible_rows = self.get_visible_row_count();
+        if caret_row < self.first_visible_row {
+            self.first_visible_row = caret_row;
+        } else if caret_row >= self.first_visible_row + visible_rows {
+            self.first_visible_row = caret_row + 1 - visible_rows;
+        }
+        self.first_visible_row = self.first_visible_row.min(self.rows.len().max(visible_rows) - visible_rows);
+    }
+
+    fn move_caret_vertically(&mut self, down: bool) {
+        let caret_row = self.get_caret_row();
+        let target_row = match down {
+            true => caret_row + 1,
+            false => caret_row.wrapping_sub(1)
+        };
+        if target_row < self.rows.len() {
+            let row = self.rows[caret_row];
+            let caret_x = self.max_region.get_float_min_x() + self.get_column_width(row.line, row.start, self.caret_column);
+            let target = self.rows[target_row];
+            self.caret_line = target.line;
+            self.caret_column = self.get_column_at(target, caret_x);
+        }
+    }
+
+    fn insert_at_caret(&mut self, text: &str) {
+        let mut new_lines = split_lines(text);
+        let first_line = self.caret_line;
+        let line = &mut self.lines[self.caret_line];
+        let offset = grapheme_offset(line, self.caret_column);
+        let remaining = line.split_off(offset);
+
+        let first_new_line = new_lines.remove(0);
+        line.push_str(&first_new_line);
+        let inserted_lines = new_lines.len();
+        for (index, new_line) in new_lines.into_iter().enumerate() {
+            self.lines.insert(self.caret_line + 1 + index, new_line);
+        }
+
+        self.caret_line += inserted_lines;
+        let line = &mut self.lines[self.caret_line];
+        let insert_end = line.len();
+        line.push_str(&remaining);
+        self.caret_column = get_caret_after_insert(line, insert_end);
+        self.update_line_widths(first_line, 1, inserted_lines + 1);
+    }
+
+    fn remove_before_caret(&mut self) {
+        if self.caret_column > 0 {
+            let line = &mut self.lines[self.caret_line];
+            let start_offset = grapheme_offset(line, self.caret_column - 1);
+            let end_offset = grapheme_offset(line, self.caret_column);
+            line.replace_range(start_offset..end_offset, "");
+            self.caret_column -= 1;
+            self.update_line_widths(self.caret_line, 1, 1);
+        } else if self.caret_line > 0 {
+            let removed_line = self.lines.remove(self.caret_line);
+            self.caret_line -= 1;
+            self.caret_column = count_graphemes(&self.lines[self.caret_line]);
+            self.lines[self.caret_line].push_str(&removed_line);
+            self.update_line_widths(self.caret_line, 2, 1);
+        }
+    }
+
+    fn remove_after_caret(&mut self) {
+        let line_length = count_graphemes(&self.lines[self.caret_line]);
+        if self.caret_column < line_length {
+            let line = &mut self.lines[self.caret_line];
+            let start_offset = grapheme_offset(line, self.caret_column);
+            let end_offset = grapheme_offset(line, self.caret_column + 1);
+            line.replace_range(start_offset..end_offset, "");
+            self.update_line_widths(self.caret_line, 1, 1);
+        } else if self.caret_line + 1 < self.lines.len() {
+            let removed_line = self.lines.remove(self.caret_line + 1);
+            self.lines[self.caret_line].push_str(&removed_line);
+            self.update_line_widths(self.caret_line, 2, 1);
+        }
+    }
+
+    fn fire_change(&mut self, old_text: &str) {
+        let new_text = self.get_current_text();
+        if old_text != new_text {
+            let agent = self.agent();
+            if let Some(on_change) = self.on_change.as_mut() {
+                on_change(&mut agent.borrow_mut(), old_text, &new_text);
+            }
+        }
+    }
+
+    fn fire_submit(&mut self) {
+        let text = self.get_current_text();
+        let agent = self.agent();
+        if let Some(on_submit) = self.on_submit.as_mut() {
+            on_submit(&mut agent.borrow_mut(), &text);
+        }
+    }
+}
+
+impl Focusable for TextAreaRenderController {
+
+    fn is_focused(&self) -> bool {
+        self.active
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.set_active(focused);
+        self.caret_time = current_time();
+        self.request_render_if_attached();
+    }
+
+    fn set_focus_handle(&mut self, handle: Option<FocusHandle>) {
+        self.focus_handle = handle;
+    }
+}
+
+impl ComponentBehavior for TextAreaRenderController {
+
+    fn attach(&mut self, agent: &mut dyn LayerAgent){
+        if let Err(error) = self.try_attach(agent) {
+            self.attach_error = Some(error);
+        }
+    }
+
+    fn set_agent(&mut self, agent: Weak<RefCell<ComponentAgent>>){
+        self.agent = Some(agent);
+    }
+
+    fn get_agent(&self) -> &Weak<RefCell<ComponentAgent>> {
+        self.agent.as_ref().expect("Agent should have been set by now")
+    }
+
+    fn render(&mut self, _params: &mut RenderParams) -> BehaviorRenderResult {
+        let actions = vec![PassedRenderAction::new(self.max_region)];
+        let result = match self.mouse_over {
+            true => BehaviorRenderResult::with_cursor(Cursor::TEXT, actions),
+            false => BehaviorRenderResult::without_cursor(actions)
+        };
+
+        let colors;
+        if self.active {
+            colors = self.active_colors;
+        } else if self.mouse_over {
+            colors = self.hover_colors;
+        } else {
+            colors = self.base_colors;
+        }
+
+        // Rows move when the text is scrolled or wrapped differently, so the whole area must be cleared
+        self.font.fill_rect(self.max_region, colors.background_color);
+
+        self.update_first_visible_row();
+        let last_visible_row = (self.first_visible_row + self.get_visible_row_count()).min(self.rows.len());
+        let min_x = self.max_region.get_float_min_x();
+        for row_index in self.first_visible_row..last_visible_row {
+            let row = self.rows[row_index];
+            let line = &self.lines[row.line];
+            let row_text = &line[grapheme_offset(line, row.start)..grapheme_offset(line, row.end)];
+            Rc::clone(&self.font).create_text_model(row_text).render(min_x, self.get_row_y(row_index), self.line_height, colors);
+        }
+
+        if self.active {
+            let caret_row = self.get_caret_row();
+            let row = self.rows[caret_row];
+            let caret_x = min_x + self.get_column_width(row.line, row.start, self.caret_column);
+            let caret_y = self.get_row_y(caret_row);
+            let caret_width = self.line_height / 16.0;
+            draw_blinking_caret(&self.font, Region::from_floats(caret_x, caret_y, caret_x + caret_width, caret_y + self.line_height),
+                    colors.fill_color, self.caret_time, &self.agent);
+        }
+        result
+    }
+
+    fn get_cursor(&mut self, _params: &mut CursorParams) -> Option<Cursor> {
+        if self.mouse_over {
+            Some(Cursor::TEXT)
+        } else {
+            None
+        }
+    }
+
+    fn mouse_move(&mut self, params: &mut MouseMoveParams) {
+        self.last_mouse_pos = params.new_mouse_pos;
+        self.update_mouse_over();
+    }
+
+    fn mouse_click_inside(&mut self, params: &mut MouseClickParams) {
+        let max_y = self.max_region.get_float_min_y() + self.max_region.get_float_height();
+        let clicked_row = self.first_visible_row + ((max_y - params.mouse_pos.get_y()) / self.line_height) as usize;
+        if let Some(row) = self.rows.get(clicked_row.min(self.rows.len() - 1)) {
+            let row = *row;
+            self.caret_line = row.line;
+            self.caret_column = self.get_column_at(row, params.mouse_pos.get_x());
+        }
+
+        if let Some(focus_handle) = &self.focus_handle {
+            focus_handle.claim_focus();
+        }
+        self.active = true;
+        self.caret_time = current_time();
+        self.request_render_if_attached();
+    }
+
+    fn mouse_click_outside(&mut self, _params: &mut MouseClickOutParams) {
+        self.active = false;
+        self.request_render_if_attached();
+    }
+
+    fn key_down(&mut self, params: &mut KeyDownParams) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+
+        let key = params.keys.get_key();
+        if key == "Tab" {
+
+            // Leave focus traversal to the focus manager
+            return false;
+        }
+        let old_text = self.get_current_text();
+        let mut submitted = false;
+        if params.keys.is_control_down() {
+            match (key, self.submit_on_control_enter) {
+                ("Enter", true) => {
+                    self.set_active(false);
+                    submitted = true;
+                },
+
+                // Leave the other shortcuts (like copy and paste) to the browser
+                _ => return false
+            }
+        } else if is_typed_key(key) {
+            self.insert_at_caret(key);
+        } else {
+            match key {
+                "Enter" => self.insert_at_caret("\n"),
+                "Backspace" => self.remove_before_caret(),
+                "Delete" => self.remove_after_caret(),
+                "ArrowLeft" => if self.caret_column > 0 {
+                    self.caret_column -= 1;
+                } else if self.caret_line > 0 {
+                    self.caret_line -= 1;
+                    self.caret_column = count_graphemes(&self.lines[self.caret_line]);
+                },
+                "ArrowRight" => if self.caret_column < count_graphemes(&self.lines[self.caret_line]) {
+                    self.caret_column += 1;
+                } else if self.caret_line + 1 < self.lines.len() {
+                    self.caret_line += 1;
+                    self.caret_column = 0;
+                },
+                "ArrowUp" => self.move_caret_vertically(false),
+                "ArrowDown" => self.move_caret_vertically(true),
+                "Home" => self.caret_column = 0,
+                "End" => self.caret_column = count_graphemes(&self.lines[self.caret_line]),
+                "Escape" => self.set_active(false),
+                _ => {}
+            }
+        }
+
+        self.caret_time = current_time();
+        self.request_render_if_attached();
+        self.fire_change(&old_text);
+        if submitted {
+            self.fire_submit();
+        }
+        true
+    }
+
+    fn on_paste(&mut self, clipboard: &ClipboardData) -> bool {
+        match self.is_active() {
+            true => match clipboard {
+                ClipboardData::Text(text_to_paste) => {
+                    let old_text = self.get_current_text();
+                    self.insert_at_caret(text_to_paste);
+                    self.caret_time = current_time();
+                    self.request_render_if_attached();
+                    self.fire_change(&old_text);
+                    true
+                }
+            }, false => false
+        }
+    }
+}
\ No newline at end of file
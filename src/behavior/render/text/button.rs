@@ -7,6 +7,8 @@ use wasmuri_text::*;
 
 use super::*;
 
+use crate::{ComponentError, upgrade_agent};
+
 pub struct ButtonTextRenderController {
 
     region: TextRegionProps,
@@ -15,8 +17,21 @@ pub struct ButtonTextRenderController {
 
     base_colors: TextColors,
     hover_colors: TextColors,
+    pressed_colors: TextColors,
+    disabled_colors: TextColors,
+    checked_colors: Option<(TextColors, TextColors, TextColors)>,
+    render_opacity: Option<RenderOpacity>,
 
+    visible: bool,
+    attach_error: Option<ComponentError>,
     mouse_over: bool,
+    last_mouse_pos: Option<Vector2f>,
+    checked: bool,
+    enabled: bool,
+    pressed: bool,
+    focused: bool,
+    focus_color: Color,
+    focus_drawn: bool
 }
 
 fn lighten_component(component: u8) -> u8 {
@@ -31,6 +46,23 @@ fn lighten_colors(colors: TextColors) -> TextColors {
     TextColors::new(lighten_color(colors.fill_color), lighten_color(colors.stroke_color), lighten_color(colors.background_color))
 }
 
+fn darken_component(component: u8) -> u8 {
+    ((component as u16 * 3) / 4) as u8
+}
+
+fn darken_color(color: Color) -> Color {
+    Color::from_rgba(darken_component(color.get_red()), darken_component(color.get_green()), darken_component(color.get_blue()), color.get_alpha())
+}
+
+fn darken_colors(colors: TextColors) -> TextColors {
+    TextColors::new(darken_color(colors.fill_color), darken_color(colors.stroke_color), darken_color(colors.background_color))
+}
+
+// Returns the normal, hover and pressed colors of a button with the given colors
+fn derive_state_colors(colors: TextColors) -> (TextColors, TextColors, TextColors) {
+    (colors, lighten_colors(colors), darken_colors(colors))
+}
+
 impl ButtonTextRenderController {
 
     pub fn new(text: &str, font: &Rc<Font>, region: TextRegionProps, base_colors: TextColors, hover_colors: TextColors) -> ButtonTextRenderController {
@@ -41,9 +73,21 @@ impl ButtonTextRenderController {
 
             base_colors,
             hover_colors,
-
-            // TODO Handle the case where the mouse hovers over it right away
-            mouse_over: false
+            pressed_colors: darken_colors(base_colors),
+            disabled_colors: to_disabled_colors(base_colors),
+            checked_colors: None,
+            render_opacity: None,
+
+            visible: true,
+            attach_error: None,
+            mouse_over: false,
+            last_mouse_pos: None,
+            checked: false,
+            enabled: true,
+            pressed: false,
+            focused: false,
+            focus_color: Color::from_rgb(70, 130, 230),
+            focus_drawn: false
         }
     }
 
@@ -64,8 +108,6 @@ impl ButtonTextRenderController {
         (Rc::clone(&instance) as Rc<RefCell<dyn ComponentBehavior>>, instance)
     }
 
-    // TODO Update render opacity if necessary!
-
     pub fn simple_tuple(text: &str, font: &Rc<Font>, region: TextRegionProps, colors: TextColors) -> (Rc<RefCell<dyn ComponentBehavior>>, Rc<RefCell<dyn TextRenderController>>) {
         let instance = Rc::new(RefCell::new(Self::simple(text, font, region, colors)));
         (Rc::clone(&instance) as Rc<RefCell<dyn ComponentBehavior>>, instance)
@@ -73,80 +115,189 @@ impl ButtonTextRenderController {
 
     pub fn set_base_fill_color(&mut self, new_color: Color){
         self.base_colors.fill_color = new_color;
-        self.request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_base_stroke_color(&mut self, new_color: Color){
         self.base_colors.stroke_color = new_color;
-        self.request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_base_background_color(&mut self, new_color: Color){
         self.base_colors.background_color = new_color;
-        self.request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_base_colors(&mut self, new_colors: TextColors){
         self.base_colors = new_colors;
-        self.request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_hover_fill_color(&mut self, new_color: Color){
         self.hover_colors.fill_color = new_color;
-        self.request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_hover_stroke_color(&mut self, new_color: Color){
         self.hover_colors.stroke_color = new_color;
-        self.request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_hover_background_color(&mut self, new_color: Color){
         self.hover_colors.background_color = new_color;
-        self.request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_hover_colors(&mut self, new_colors: TextColors){
         self.hover_colors = new_colors;
-        self.request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_pressed_fill_color(&mut self, new_color: Color){
+        self.pressed_colors.fill_color = new_color;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_pressed_stroke_color(&mut self, new_color: Color){
+        self.pressed_colors.stroke_color = new_color;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_pressed_background_color(&mut self, new_color: Color){
+        self.pressed_colors.background_color = new_color;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_pressed_colors(&mut self, new_colors: TextColors){
+        self.pressed_colors = new_colors;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_disabled_colors(&mut self, new_colors: TextColors){
+        self.disabled_colors = new_colors;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_checked_colors(&mut self, new_colors: Option<TextColors>){
+        self.checked_colors = new_colors.map(derive_state_colors);
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_fill_color(&mut self, new_color: Color){
         self.base_colors.fill_color = new_color;
         self.hover_colors.fill_color = lighten_color(new_color);
-        self.request_render();
+        self.pressed_colors.fill_color = darken_color(new_color);
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_stroke_color(&mut self, new_color: Color){
         self.base_colors.stroke_color = new_color;
         self.hover_colors.stroke_color = lighten_color(new_color);
-        self.request_render();
+        self.pressed_colors.stroke_color = darken_color(new_color);
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_background_color(&mut self, new_color: Color){
         self.base_colors.background_color = new_color;
         self.hover_colors.background_color = lighten_color(new_color);
-        self.request_render();
+        self.pressed_colors.background_color = darken_color(new_color);
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_colors(&mut self, new_colors: TextColors){
-        self.base_colors = new_colors;
-        self.hover_colors = lighten_colors(new_colors);
-        self.request_render();
+        let (base_colors, hover_colors, pressed_colors) = derive_state_colors(new_colors);
+        self.base_colors = base_colors;
+        self.hover_colors = hover_colors;
+        self.pressed_colors = pressed_colors;
+        self.disabled_colors = to_disabled_colors(new_colors);
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_focus_color(&mut self, new_color: Color){
+        self.focus_color = new_color;
+        self.request_render_if_attached();
+    }
+
+    fn get_all_colors(&self) -> Vec<TextColors> {
+        let mut all_colors = vec![self.base_colors, self.hover_colors, self.pressed_colors, self.disabled_colors];
+        if let Some((checked_colors, checked_hover_colors, checked_pressed_colors)) = self.checked_colors {
+            all_colors.push(checked_colors);
+            all_colors.push(checked_hover_colors);
+            all_colors.push(checked_pressed_colors);
+        }
+        all_colors
+    }
+
+    fn update_render_opacity(&mut self){
+        if let Some(agent_cell) = self.agent.as_ref().filter(|_| self.visible).and_then(|agent| agent.upgrade()) {
+            let all_colors = self.get_all_colors();
+            update_render_opacity(&mut agent_cell.borrow_mut(), self.get_max_region(), &mut self.render_opacity, all_colors, "ButtonTextRenderController");
+        }
+    }
+
+    pub fn try_attach(&mut self, agent: &mut dyn LayerAgent) -> Result<(), ComponentError> {
+        let render_opacity = determine_render_opacity(self.get_all_colors());
+
+        // Hidden buttons claim their spaces when they are shown
+        if self.visible {
+            agent.claim_render_space(self.region.get_max_region(), RenderTrigger::Request, render_opacity, RenderPhase::Text)
+                    .map_err(|_| ComponentError::RenderSpaceTaken("ButtonTextRenderController"))?;
+            agent.claim_mouse_move_space(self.region.get_max_region());
+        }
+        self.render_opacity = Some(render_opacity);
+        self.last_mouse_pos = agent.get_mouse_position();
+        self.mouse_over = self.visible && self.last_mouse_pos.is_some() && self.get_current_region().is_float_inside(self.last_mouse_pos.unwrap());
+        Ok(())
+    }
+
+    fn request_render_if_attached(&self){
+        if let Some(agent) = self.agent.as_ref().and_then(|agent| agent.upgrade()) {
+            agent.borrow_mut().request_render();
+        }
+    }
+
+    // The current region depends on the text, so this also needs to be checked when the mouse didn't move
+    fn update_mouse_over(&mut self){
+        let region = self.get_current_region();
+        let new_mouse_over = self.visible && self.last_mouse_pos.is_some() && region.is_float_inside(self.last_mouse_pos.unwrap());
+        if self.mouse_over != new_mouse_over {
+            self.mouse_over = new_mouse_over;
+            self.request_render_if_attached();
+        }
     }
 
-    fn request_render(&self){
-        self.agent.as_ref().expect("Agent should have been set by now").upgrade().expect("Agent should not have been dropped").borrow_mut().request_render();
+    fn get_state_colors(&self) -> (TextColors, TextColors, TextColors) {
+        match (self.checked, self.checked_colors) {
+            (true, Some(checked_colors)) => checked_colors,
+            _ => (self.base_colors, self.hover_colors, self.pressed_colors)
+        }
     }
 }
 
 impl ComponentBehavior for ButtonTextRenderController {
 
     fn attach(&mut self, agent: &mut dyn LayerAgent){
-        agent.claim_mouse_move_space(self.region.get_max_region());
-        agent.claim_render_space(self.region.get_max_region(), RenderTrigger::Request, 
-                determine_render_opacity(vec![self.base_colors, self.hover_colors]), 
-                RenderPhase::Text).expect("Should have render space for ButtonTextRenderController");
+        if let Err(error) = self.try_attach(agent) {
+            self.attach_error = Some(error);
+        }
     }
 
     fn get_agent(&self) -> &Weak<RefCell<ComponentAgent>> {
@@ -159,28 +310,56 @@ impl ComponentBehavior for ButtonTextRenderController {
 
     fn render(&mut self, params: &mut RenderParams) -> BehaviorRenderResult {
         let region = self.get_current_region();
-        let actions = vec![PassedRenderAction::new(region)];
+
+        // The focus outline covers the whole max region, and must be cleared after the focus is lost
+        let render_max_region = self.focused || self.focus_drawn;
+        let actions = match render_max_region {
+            true => vec![PassedRenderAction::new(self.region.get_max_region())],
+            false => vec![PassedRenderAction::new(region)]
+        };
         let colors;
         let result;
         
-        if self.mouse_over {
-            colors = self.hover_colors;
+        if !self.enabled {
+            colors = self.disabled_colors;
+            result = BehaviorRenderResult::without_cursor(actions);
+        } else if self.pressed && self.mouse_over {
+            colors = self.get_state_colors().2;
+            result = BehaviorRenderResult::with_cursor(Cursor::POINTER, actions);
+        } else if self.mouse_over {
+            colors = self.get_state_colors().1;
             result = BehaviorRenderResult::with_cursor(Cursor::POINTER, actions);
         } else {
-            colors = self.base_colors;
+            colors = self.get_state_colors().0;
             result = BehaviorRenderResult::without_cursor(actions);
         }
-
-        if self.region.should_clear_remaining(&self.text_model, params) {
+        if render_max_region || self.region.should_clear_remaining(&self.text_model, params) {
             self.text_model.get_font().fill_rect(self.region.get_max_region(), colors.background_color);
         }
 
         self.text_model.render(region.get_float_min_x(), region.get_float_min_y(), region.get_float_height(), colors);
+
+        self.focus_drawn = self.focused;
+        if self.focused {
+
+            // Draw the outline just inside the max region, since that is all the render space this button has
+            let max_region = self.region.get_max_region();
+            let min_x = max_region.get_float_min_x();
+            let min_y = max_region.get_float_min_y();
+            let max_x = min_x + max_region.get_float_width();
+            let max_y = min_y + max_region.get_float_height();
+            let thickness = max_region.get_float_height().min(max_region.get_float_width()) / 20.0;
+            let font = self.text_model.get_font();
+            font.fill_rect(Region::from_floats(min_x, min_y, max_x, min_y + thickness), self.focus_color);
+            font.fill_rect(Region::from_floats(min_x, max_y - thickness, max_x, max_y), self.focus_color);
+            font.fill_rect(Region::from_floats(min_x, min_y, min_x + thickness, max_y), self.focus_color);
+            font.fill_rect(Region::from_floats(max_x - thickness, min_y, max_x, max_y), self.focus_color);
+        }
         result
     }
 
     fn get_cursor(&mut self, _params: &mut CursorParams) -> Option<Cursor> {
-        if self.mouse_over {
+        if self.mouse_over && self.enabled {
             Some(Cursor::POINTER)
         } else {
             None
@@ -188,13 +367,8 @@ impl ComponentBehavior for ButtonTextRenderController {
     }
 
     fn mouse_move(&mut self, params: &mut MouseMoveParams) {
-        let region = self.get_current_region();
-        let next_mouse = params.new_mouse_pos;
-        let new_mouse_over = next_mouse.is_some() && region.is_float_inside(next_mouse.unwrap());
-        if self.mouse_over != new_mouse_over {
-            self.request_render();
-        }
-        self.mouse_over = new_mouse_over;
+        self.last_mouse_pos = params.new_mouse_pos;
+        self.update_mouse_over();
     }
 }
 
@@ -208,13 +382,113 @@ impl TextRenderController for ButtonTextRenderController {
         self.region.get_current_region(&self.text_model)
     }
 
+    fn set_region(&mut self, new_region: Region) -> Result<(), ComponentError> {
+        let old_region = self.region.get_max_region();
+
+        // Only attached and visible controllers have claimed spaces that need to be moved
+        let claimed = match self.render_opacity.filter(|_| self.visible) {
+            Some(render_opacity) => Some((render_opacity, upgrade_agent(&self.agent, "ButtonTextRenderController")?)),
+            None => None
+        };
+        self.region.set_region(new_region)?;
+        if let Some((render_opacity, agent_cell)) = claimed {
+            let mut agent = agent_cell.borrow_mut();
+            if let Err(error) = reclaim_render_space(&mut agent, old_region, new_region, render_opacity, "ButtonTextRenderController") {
+                self.region.set_region(old_region).expect("Should be able to move back to the old region");
+                return Err(error);
+            }
+            agent.release_mouse_move_space(old_region);
+            agent.claim_mouse_move_space(new_region);
+            agent.request_render();
+        }
+        self.update_mouse_over();
+        Ok(())
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) -> Result<(), ComponentError> {
+        if self.visible == visible {
+            return Ok(());
+        }
+
+        // Only attached controllers have spaces to give up or claim
+        if self.render_opacity.is_some() {
+            let region = self.region.get_max_region();
+            let all_colors = self.get_all_colors();
+            let agent_cell = upgrade_agent(&self.agent, "ButtonTextRenderController")?;
+            let mut agent = agent_cell.borrow_mut();
+            set_render_space_visible(&mut agent, region, visible, all_colors, &mut self.render_opacity, "ButtonTextRenderController")?;
+            match visible {
+                true => agent.claim_mouse_move_space(region),
+                false => agent.release_mouse_move_space(region)
+            };
+            agent.request_render();
+        }
+        self.visible = visible;
+        if !visible {
+            self.mouse_over = false;
+            self.pressed = false;
+            self.focused = false;
+            self.focus_drawn = false;
+        }
+        if visible {
+            self.update_mouse_over();
+        }
+        Ok(())
+    }
+
+    fn take_attach_error(&mut self) -> Option<ComponentError> {
+        self.attach_error.take()
+    }
+
     fn set_text(&mut self, new_text: &str){
         self.text_model = Rc::clone(self.text_model.get_font()).create_text_model(new_text);
-        self.request_render();
+        self.update_mouse_over();
+        self.request_render_if_attached();
     }
 
     fn set_text_model(&mut self, new_text: TextModel){
         self.text_model = new_text;
-        self.request_render();
+        self.update_mouse_over();
+        self.request_render_if_attached();
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.pressed = false;
+            self.focused = false;
+        }
+        self.request_render_if_attached();
+    }
+
+    fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+        self.request_render_if_attached();
+    }
+
+    fn set_pressed(&mut self, pressed: bool) {
+        if self.pressed != pressed {
+            self.pressed = pressed;
+            self.request_render_if_attached();
+        }
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+
+        // Hidden buttons can't show a focus ring
+        self.focused = focused && self.visible;
+        self.request_render_if_attached();
     }
 }
\ No newline at end of file
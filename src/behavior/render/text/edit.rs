@@ -8,6 +8,22 @@ use wasmuri_core::*;
 use wasmuri_text::*;
 
 use super::*;
+use super::history::*;
+
+use crate::{ComponentError, upgrade_agent};
+use crate::behavior::{FocusHandle, reclaim_click_space};
+use crate::time::current_time;
+
+pub type EditChangeAction = Box<dyn FnMut(&mut ComponentAgent, &str, &str)>;
+
+pub type EditSubmitAction = Box<dyn FnMut(&mut ComponentAgent, &str)>;
+
+pub trait CompositionHook {
+
+    fn activate(&self);
+
+    fn deactivate(&self);
+}
 
 pub struct EditTextRenderController {
 
@@ -18,34 +34,117 @@ pub struct EditTextRenderController {
     base_colors: TextColors,
     hover_colors: TextColors,
     active_colors: TextColors,
+    error_colors: TextColors,
+    placeholder_colors: TextColors,
+    disabled_colors: TextColors,
+    render_opacity: Option<RenderOpacity>,
+    attach_error: Option<ComponentError>,
 
+    visible: bool,
     active: bool,
+    enabled: bool,
     mouse_over: bool,
-    current_text: String
+    last_mouse_pos: Option<Vector2f>,
+    current_text: String,
+    display_text: String,
+    prefix_widths: Vec<f32>,
+    visible_model: Option<CachedSubModel>,
+    selected_model: Option<CachedSubModel>,
+    placeholder: Option<TextModel>,
+
+    caret: usize,
+    caret_time: f64,
+
+    selection_anchor: Option<usize>,
+    selection_color: Color,
+    dragging: bool,
+
+    last_click_time: f64,
+    click_count: u8,
+
+    history: EditHistory,
+
+    on_change: Option<EditChangeAction>,
+    on_submit: Option<EditSubmitAction>,
+
+    validator: Option<Box<dyn TextValidator>>,
+
+    scroll_mode: bool,
+    scroll_start: usize,
+
+    mask: Option<char>,
+    reveal_duration: f64,
+    revealed_grapheme: Option<usize>,
+    reveal_time: f64,
+
+    composition: Option<String>,
+    composition_hook: Option<Rc<dyn CompositionHook>>,
+
+    focus_handle: Option<FocusHandle>
 }
 
-fn lighten_component(component: u8) -> u8 {
-    ((component as u16 + 255) / 2) as u8
+const MULTI_CLICK_INTERVAL: f64 = 500.0;
+
+struct CachedSubModel {
+
+    first: usize,
+    last: usize,
+    model: TextModel
 }
 
-fn lighten_color(color: Color) -> Color {
-    Color::from_rgba(lighten_component(color.get_red()), lighten_component(color.get_green()), lighten_component(color.get_blue()), lighten_component(color.get_alpha()))
+// Active fields render every frame, so the text models of their visible and selected parts are kept until those change
+fn get_sub_model<'a>(cache: &'a mut Option<CachedSubModel>, font: &Rc<Font>, display_text: &str, first: usize, last: usize) -> &'a TextModel {
+    if cache.as_ref().is_some_and(|cached| cached.first != first || cached.last != last) {
+        *cache = None;
+    }
+    &cache.get_or_insert_with(|| {
+        let text = &display_text[grapheme_offset(display_text, first)..grapheme_offset(display_text, last)];
+        CachedSubModel { first, last, model: Rc::clone(font).create_text_model(text) }
+    }).model
 }
 
-fn to_active_colors(colors: TextColors) -> TextColors {
-    TextColors::new(colors.fill_color, colors.stroke_color, lighten_color(colors.background_color))
+fn to_error_colors(colors: TextColors) -> TextColors {
+    TextColors::new(colors.fill_color, colors.stroke_color, Color::from_rgba(255, 170, 170, colors.background_color.get_alpha()))
 }
 
-fn darken_component(component: u8) -> u8 {
-    ((component as u16 * 19) / 20) as u8
+fn to_placeholder_colors(colors: TextColors) -> TextColors {
+    TextColors::new(lighten_color(colors.fill_color), lighten_color(colors.stroke_color), colors.background_color)
 }
 
-fn darken_color(color: Color) -> Color {
-    Color::from_rgba(darken_component(color.get_red()), darken_component(color.get_green()), darken_component(color.get_blue()), color.get_alpha())
+fn word_bounds(text: &str, grapheme_index: usize) -> (usize, usize) {
+    let length = count_graphemes(text);
+    let offset = grapheme_offset(text, grapheme_index.min(length.max(1) - 1));
+    for (word_offset, word) in UnicodeSegmentation::split_word_bound_indices(text) {
+        if offset < word_offset + word.len() {
+            return (count_graphemes(&text[..word_offset]), count_graphemes(&text[..word_offset + word.len()]));
+        }
+    }
+    (length, length)
+}
+
+fn previous_word_start(text: &str, grapheme_index: usize) -> usize {
+    let offset = grapheme_offset(text, grapheme_index);
+    let mut result = 0;
+    for (word_offset, word) in UnicodeSegmentation::split_word_bound_indices(text) {
+        if word_offset >= offset {
+            break;
+        }
+        if !word.trim().is_empty() {
+            result = word_offset;
+        }
+    }
+    count_graphemes(&text[..result])
 }
 
-fn to_hover_colors(colors: TextColors) -> TextColors {
-    TextColors::new(colors.fill_color, colors.stroke_color, darken_color(colors.background_color))
+fn next_word_end(text: &str, grapheme_index: usize) -> usize {
+    let offset = grapheme_offset(text, grapheme_index);
+    for (word_offset, word) in UnicodeSegmentation::split_word_bound_indices(text) {
+        let word_end = word_offset + word.len();
+        if word_end > offset && !word.trim().is_empty() {
+            return count_graphemes(&text[..word_end]);
+        }
+    }
+    count_graphemes(text)
 }
 
 impl EditTextRenderController {
@@ -59,11 +158,53 @@ impl EditTextRenderController {
             base_colors,
             hover_colors,
             active_colors,
+            error_colors: to_error_colors(base_colors),
+            placeholder_colors: to_placeholder_colors(base_colors),
+            disabled_colors: to_disabled_colors(base_colors),
+            render_opacity: None,
+            attach_error: None,
 
+            visible: true,
             active: false,
-            // TODO Handle the case where mouse_over should be true initially
+            enabled: true,
             mouse_over: false,
-            current_text: text.to_string()
+            last_mouse_pos: None,
+            current_text: text.to_string(),
+            display_text: text.to_string(),
+            prefix_widths: measure_prefix_widths(font, text),
+            visible_model: None,
+            selected_model: None,
+            placeholder: None,
+
+            caret: count_graphemes(text),
+            caret_time: 0.0,
+
+            selection_anchor: None,
+            selection_color: Color::from_rgb(170, 200, 255),
+            dragging: false,
+
+            last_click_time: 0.0,
+            click_count: 0,
+
+            history: EditHistory::new(),
+
+            on_change: None,
+            on_submit: None,
+
+            validator: None,
+
+            scroll_mode: false,
+            scroll_start: 0,
+
+            mask: None,
+            reveal_duration: 0.0,
+            revealed_grapheme: None,
+            reveal_time: 0.0,
+
+            composition: None,
+            composition_hook: None,
+
+            focus_handle: None
         }
     }
 
@@ -89,94 +230,147 @@ impl EditTextRenderController {
         (Rc::clone(&instance) as Rc<RefCell<dyn ComponentBehavior>>, instance)
     }
 
-    // TODO Update render opacity if necessary!
-
     pub fn set_base_fill_color(&mut self, new_color: Color){
         self.base_colors.fill_color = new_color;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_base_stroke_color(&mut self, new_color: Color){
         self.base_colors.stroke_color = new_color;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_base_background_color(&mut self, new_color: Color){
         self.base_colors.background_color = new_color;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_base_colors(&mut self, new_colors: TextColors){
         self.base_colors = new_colors;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_hover_fill_color(&mut self, new_color: Color){
         self.hover_colors.fill_color = new_color;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_hover_stroke_color(&mut self, new_color: Color){
         self.hover_colors.stroke_color = new_color;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_hover_background_color(&mut self, new_color: Color){
         self.hover_colors.background_color = new_color;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_hover_colors(&mut self, new_colors: TextColors){
         self.hover_colors = new_colors;
-        self.agent().borrow_mut().request_render();    
+        self.update_render_opacity();
+        self.request_render_if_attached();    
     }
 
     pub fn set_active_fill_color(&mut self, new_color: Color){
         self.active_colors.fill_color = new_color;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_active_stroke_color(&mut self, new_color: Color){
         self.active_colors.stroke_color = new_color;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_active_background_color(&mut self, new_color: Color){
         self.active_colors.background_color = new_color;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_active_colors(&mut self, new_colors: TextColors){
         self.active_colors = new_colors;
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_error_fill_color(&mut self, new_color: Color){
+        self.error_colors.fill_color = new_color;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_error_stroke_color(&mut self, new_color: Color){
+        self.error_colors.stroke_color = new_color;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_error_background_color(&mut self, new_color: Color){
+        self.error_colors.background_color = new_color;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_error_colors(&mut self, new_colors: TextColors){
+        self.error_colors = new_colors;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_placeholder_colors(&mut self, new_colors: TextColors){
+        self.placeholder_colors = new_colors;
+        self.update_render_opacity();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_disabled_colors(&mut self, new_colors: TextColors){
+        self.disabled_colors = new_colors;
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_fill_color(&mut self, new_color: Color){
         self.base_colors.fill_color = new_color;
         self.hover_colors.fill_color = darken_color(new_color);
         self.active_colors.fill_color = lighten_color(new_color);
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_stroke_color(&mut self, new_color: Color){
         self.base_colors.stroke_color = new_color;
         self.hover_colors.stroke_color = darken_color(new_color);
         self.active_colors.stroke_color = lighten_color(new_color);
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_background_color(&mut self, new_color: Color){
         self.base_colors.background_color = new_color;
         self.hover_colors.background_color = darken_color(new_color);
         self.active_colors.background_color = lighten_color(new_color);
-        self.agent().borrow_mut().request_render();
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn set_colors(&mut self, new_colors: TextColors){
         self.base_colors = new_colors;
         self.hover_colors = to_hover_colors(new_colors);
         self.active_colors = to_active_colors(new_colors);
-        self.agent().borrow_mut().request_render();
+        self.error_colors = to_error_colors(new_colors);
+        self.placeholder_colors = to_placeholder_colors(new_colors);
+        self.disabled_colors = to_disabled_colors(new_colors);
+        self.update_render_opacity();
+        self.request_render_if_attached();
     }
 
     pub fn is_active(&self) -> bool {
@@ -184,35 +378,475 @@ impl EditTextRenderController {
     }
 
     pub fn set_active(&mut self, new_active: bool) {
+
+        // Disabled and hidden fields can't be edited
+        let new_active = new_active && self.enabled && self.visible;
+        if self.active != new_active {
+            if let Some(hook) = &self.composition_hook {
+                match new_active {
+                    true => hook.activate(),
+                    false => hook.deactivate()
+                };
+            }
+        }
+        if !new_active && self.composition.take().is_some() {
+            self.rebuild_text_model();
+        }
         self.active = new_active;
     }
 
+    pub fn set_composition_hook(&mut self, composition_hook: Option<Rc<dyn CompositionHook>>) {
+        self.composition_hook = composition_hook;
+    }
+
+    pub fn is_composing(&self) -> bool {
+        self.composition.is_some()
+    }
+
+    pub fn start_composition(&mut self) {
+        if self.active {
+            let previous_state = self.get_edit_state();
+            self.delete_selection();
+            self.record_edit(previous_state, EditKind::Other);
+            self.composition = Some(String::new());
+            self.update_text();
+        }
+    }
+
+    pub fn update_composition(&mut self, composed_text: &str) {
+        if self.composition.is_some() {
+            self.composition = Some(composed_text.to_string());
+            self.caret_time = current_time();
+            self.update_text();
+        }
+    }
+
+    pub fn end_composition(&mut self, composed_text: &str) {
+        if self.composition.take().is_some() {
+            let previous_state = self.get_edit_state();
+            self.insert_at_caret(composed_text);
+            self.record_edit(previous_state, EditKind::Other);
+            self.caret_time = current_time();
+            self.update_text();
+        }
+    }
+
     pub fn get_current_text(&self) -> &str {
         &self.current_text
     }
 
+    pub fn set_validator(&mut self, validator: Option<Box<dyn TextValidator>>) {
+        self.validator = validator;
+        self.request_render_if_attached();
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match &self.validator {
+            Some(validator) => validator.is_valid(&self.current_text),
+            None => true
+        }
+    }
+
+    pub fn set_placeholder(&mut self, placeholder: Option<&str>) {
+        self.placeholder = placeholder.map(|placeholder| Rc::clone(self.text_model.get_font()).create_text_model(placeholder));
+        self.request_render_if_attached();
+    }
+
+    pub fn is_scroll_mode(&self) -> bool {
+        self.scroll_mode
+    }
+
+    pub fn set_scroll_mode(&mut self, scroll_mode: bool) {
+        self.scroll_mode = scroll_mode;
+        self.scroll_start = 0;
+        self.request_render_if_attached();
+    }
+
+    pub fn is_masked(&self) -> bool {
+        self.mask.is_some()
+    }
+
+    pub fn set_mask(&mut self, mask: Option<char>) {
+        self.mask = mask;
+        self.revealed_grapheme = None;
+
+        // The mask is usually chosen before the component is attached
+        self.rebuild_text_model();
+        self.request_render_if_attached();
+    }
+
+    pub fn set_reveal_duration(&mut self, reveal_duration: f64) {
+        self.reveal_duration = reveal_duration;
+    }
+
+    pub fn get_caret(&self) -> usize {
+        self.caret
+    }
+
+    pub fn set_caret(&mut self, new_caret: usize) {
+        self.caret = new_caret.min(count_graphemes(&self.current_text));
+        self.caret_time = current_time();
+        self.selection_anchor = None;
+        self.request_render_if_attached();
+    }
+
+    pub fn get_selection(&self) -> Option<(usize, usize)> {
+        match self.selection_anchor {
+            Some(anchor) => match anchor != self.caret {
+                true => Some((anchor.min(self.caret), anchor.max(self.caret))),
+                false => None
+            }, None => None
+        }
+    }
+
+    pub fn get_selected_text(&self) -> Option<&str> {
+        self.get_selection().map(|(start, end)| {
+            &self.current_text[grapheme_offset(&self.current_text, start)..grapheme_offset(&self.current_text, end)]
+        })
+    }
+
+    pub fn select(&mut self, start: usize, end: usize) {
+        let length = count_graphemes(&self.current_text);
+        self.selection_anchor = Some(start.min(length));
+        self.caret = end.min(length);
+        self.caret_time = current_time();
+        self.request_render_if_attached();
+    }
+
+    pub fn select_all(&mut self) {
+        self.select(0, count_graphemes(&self.current_text));
+    }
+
+    pub fn set_on_change(&mut self, on_change: Option<EditChangeAction>) {
+        self.on_change = on_change;
+    }
+
+    pub fn set_on_submit(&mut self, on_submit: Option<EditSubmitAction>) {
+        self.on_submit = on_submit;
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(state) = self.history.undo(self.get_edit_state()) {
+            self.set_edit_state(state);
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(state) = self.history.redo(self.get_edit_state()) {
+            self.set_edit_state(state);
+        }
+    }
+
+    pub fn set_selection_color(&mut self, new_color: Color) {
+        self.selection_color = new_color;
+        self.request_render_if_attached();
+    }
+
     fn agent(&self) -> Rc<RefCell<ComponentAgent>> {
         self.agent.as_ref().expect("Component agent should have been set by now").upgrade().expect("Component agent should not have been dropped")
     }
 
+    fn get_all_colors(&self) -> Vec<TextColors> {
+        vec![self.base_colors, self.hover_colors, self.active_colors, self.error_colors, self.placeholder_colors, self.disabled_colors]
+    }
+
+    fn update_render_opacity(&mut self){
+        if let Some(agent_cell) = self.agent.as_ref().filter(|_| self.visible).and_then(|agent| agent.upgrade()) {
+            let all_colors = self.get_all_colors();
+            update_render_opacity(&mut agent_cell.borrow_mut(), self.get_max_region(), &mut self.render_opacity, all_colors, "EditTextRenderController");
+        }
+    }
+
+    pub fn try_attach(&mut self, agent: &mut dyn LayerAgent) -> Result<(), ComponentError> {
+        let render_opacity = determine_render_opacity(self.get_all_colors());
+
+        // Hidden fields claim their spaces when they are shown
+        if self.visible {
+            let region = self.region.get_max_region();
+            agent.claim_render_space(region, RenderTrigger::Request, render_opacity, 
+                    RenderPhase::Text).map_err(|_| ComponentError::RenderSpaceTaken("EditTextRenderController"))?;
+            if agent.claim_mouse_click_space(region).is_err() {
+
+                // Don't leave the field half attached
+                agent.release_render_space(region);
+                return Err(ComponentError::ClickSpaceTaken("EditTextRenderController"));
+            }
+            agent.claim_mouse_in_out_space(region);
+            agent.make_key_down_listener(10);
+            agent.make_copy_listener(50);
+            agent.make_paste_listener(50);
+            agent.make_cut_listener(50);
+        }
+        self.render_opacity = Some(render_opacity);
+        self.last_mouse_pos = agent.get_mouse_position();
+        self.mouse_over = self.visible && self.last_mouse_pos.is_some() && self.get_max_region().is_float_inside(self.last_mouse_pos.unwrap());
+        Ok(())
+    }
+
+    fn request_render_if_attached(&self) {
+        if let Some(agent) = self.agent.as_ref().and_then(|agent| agent.upgrade()) {
+            agent.borrow_mut().request_render();
+        }
+    }
+
+    fn update_mouse_over(&mut self){
+        let region = self.get_max_region();
+        let new_mouse_over = self.visible && self.last_mouse_pos.is_some() && region.is_float_inside(self.last_mouse_pos.unwrap());
+
+        // Active edit fields render continuously anyway
+        if !self.active && self.mouse_over != new_mouse_over {
+            self.request_render_if_attached();
+        }
+        self.mouse_over = new_mouse_over;
+    }
+
+    fn rebuild_text_model(&mut self){
+        self.display_text = self.create_display_text();
+        self.text_model = Rc::clone(self.text_model.get_font()).create_text_model(&self.display_text);
+        self.prefix_widths = measure_prefix_widths(self.text_model.get_font(), &self.display_text);
+        self.visible_model = None;
+        self.selected_model = None;
+    }
+
     fn update_text(&mut self){
-        self.text_model = Rc::clone(self.text_model.get_font()).create_text_model(&self.current_text);
-        self.agent().borrow_mut().request_render();
+        self.rebuild_text_model();
+        self.request_render_if_attached();
+    }
+
+    fn create_display_text(&self) -> String {
+        let mut text = self.current_text.clone();
+        if let Some(composition) = &self.composition {
+            text.insert_str(grapheme_offset(&text, self.caret), composition);
+        }
+        match self.mask {
+            Some(mask) => UnicodeSegmentation::graphemes(text.as_str(), true).enumerate().map(|(index, grapheme)| {
+                match self.revealed_grapheme == Some(index) {
+                    true => grapheme.to_string(),
+                    false => mask.to_string()
+                }
+            }).collect(),
+            None => text
+        }
+    }
+
+    fn get_composition_length(&self) -> usize {
+        match &self.composition {
+            Some(composition) => count_graphemes(composition),
+            None => 0
+        }
+    }
+
+    fn get_edit_state(&self) -> EditState {
+        EditState {
+            text: self.current_text.clone(),
+            caret: self.caret,
+            selection_anchor: self.selection_anchor
+        }
+    }
+
+    fn set_edit_state(&mut self, state: EditState) {
+        let old_text = std::mem::replace(&mut self.current_text, state.text);
+        self.fire_change(&old_text);
+        self.caret = state.caret;
+        self.selection_anchor = state.selection_anchor;
+        self.caret_time = current_time();
+        self.update_text();
+    }
+
+    fn fire_change(&mut self, old_text: &str) {
+        if old_text != self.current_text {
+            let agent = self.agent();
+            if let Some(on_change) = self.on_change.as_mut() {
+                on_change(&mut agent.borrow_mut(), old_text, &self.current_text);
+            }
+        }
+    }
+
+    fn fire_submit(&mut self) {
+        let agent = self.agent();
+        if let Some(on_submit) = self.on_submit.as_mut() {
+            on_submit(&mut agent.borrow_mut(), &self.current_text);
+        }
+    }
+
+    fn accepts_input(&self, old_text: &str) -> bool {
+        match &self.validator {
+            Some(validator) => validator.accepts_input(old_text, &self.current_text),
+            None => true
+        }
+    }
+
+    fn record_edit(&mut self, previous_state: EditState, kind: EditKind) -> bool {
+        if self.current_text != previous_state.text {
+            if self.accepts_input(&previous_state.text) {
+                self.fire_change(&previous_state.text);
+                self.history.record(previous_state, kind);
+                true
+            } else {
+                self.current_text = previous_state.text;
+                self.caret = previous_state.caret;
+                self.selection_anchor = previous_state.selection_anchor;
+                false
+            }
+        } else {
+            self.history.break_merge();
+            false
+        }
+    }
+
+    fn insert_at_caret(&mut self, text: &str){
+        let offset = grapheme_offset(&self.current_text, self.caret);
+        self.current_text.insert_str(offset, text);
+        self.caret = get_caret_after_insert(&self.current_text, offset + text.len());
+    }
+
+    fn remove_graphemes(&mut self, start: usize, end: usize){
+        let start_offset = grapheme_offset(&self.current_text, start);
+        let end_offset = grapheme_offset(&self.current_text, end);
+        self.current_text.replace_range(start_offset..end_offset, "");
+    }
+
+    fn get_prefix_width(&self, graphemes: usize, render_height: f32) -> f32 {
+        self.prefix_widths[graphemes.min(self.prefix_widths.len() - 1)] * render_height
+    }
+
+    fn get_render_height(&self, region: Region) -> f32 {
+        let render_width = self.text_model.get_render_width(region.get_float_height());
+        if render_width <= region.get_float_width() {
+            region.get_float_height()
+        } else {
+            region.get_float_height() * region.get_float_width() / render_width
+        }
+    }
+
+    fn is_scrolling(&self) -> bool {
+        self.scroll_mode && self.region.needs_scroll_offset(&self.text_model)
+    }
+
+    fn get_text_placement(&self) -> (f32, f32, f32) {
+        if self.is_scrolling() {
+            let max_region = self.get_max_region();
+            let render_height = max_region.get_float_height();
+            (max_region.get_float_min_x() - self.get_prefix_width(self.scroll_start, render_height), max_region.get_float_min_y(), render_height)
+        } else {
+            let region = self.get_current_region();
+            (region.get_float_min_x(), region.get_float_min_y(), self.get_render_height(region))
+        }
+    }
+
+    // There is no way to clip the text model, so the field scrolls by whole graphemes to never cut one off at the left edge
+    fn update_scroll_offset(&mut self) {
+        if self.is_scrolling() {
+            let max_region = self.get_max_region();
+            let render_height = max_region.get_float_height();
+            let visible_width = max_region.get_float_width();
+            let length = self.prefix_widths.len() - 1;
+            let fits = |first: usize, last: usize| self.get_prefix_width(last, render_height) - self.get_prefix_width(first, render_height) <= visible_width;
+
+            // Keep the caret visible while editing, and the end of the text otherwise
+            let target = match self.active {
+                true => (self.caret + self.get_composition_length()).min(length),
+                false => length
+            };
+            let mut scroll_start = self.scroll_start.min(target);
+            while scroll_start < target && !fits(scroll_start, target) {
+                scroll_start += 1;
+            }
+
+            // Don't leave space unused at the end when the text could fill it
+            while scroll_start > 0 && fits(scroll_start - 1, length) {
+                scroll_start -= 1;
+            }
+            self.scroll_start = scroll_start;
+        } else {
+            self.scroll_start = 0;
+        }
+    }
+
+    fn get_visible_graphemes(&self, start: usize, end: usize, render_height: f32) -> (usize, usize) {
+        if self.is_scrolling() {
+
+            // Only the graphemes that fit entirely can be rendered, so a small part at the right edge can stay empty
+            let visible_width = self.get_max_region().get_float_width();
+            let scroll_width = self.get_prefix_width(self.scroll_start, render_height);
+            let first = start.max(self.scroll_start).min(end);
+            let mut last = end;
+            while last > first && self.get_prefix_width(last, render_height) - scroll_width > visible_width {
+                last -= 1;
+            }
+            (first, last)
+        } else {
+            (start, end)
+        }
+    }
+
+    fn render_graphemes(&mut self, start: usize, end: usize, selected: bool, colors: TextColors) {
+        let (text_x, text_y, render_height) = self.get_text_placement();
+        let (first, last) = self.get_visible_graphemes(start, end, render_height);
+        if first < last {
+            let model_x = text_x + self.get_prefix_width(first, render_height);
+            let cache = match selected {
+                true => &mut self.selected_model,
+                false => &mut self.visible_model
+            };
+            get_sub_model(cache, self.text_model.get_font(), &self.display_text, first, last).render(model_x, text_y, render_height, colors);
+        }
+    }
+
+    fn get_grapheme_at(&self, x: f32) -> usize {
+        let (text_x, _, render_height) = self.get_text_placement();
+        let length = count_graphemes(&self.current_text);
+        get_closest_boundary(x - text_x, 0, length, |index| self.get_prefix_width(index, render_height))
+    }
+
+    fn get_previous_word_start(&self) -> usize {
+
+        // Masked text shouldn't reveal where its words are
+        match self.is_masked() {
+            true => 0,
+            false => previous_word_start(&self.current_text, self.caret)
+        }
+    }
+
+    fn get_next_word_end(&self) -> usize {
+        match self.is_masked() {
+            true => count_graphemes(&self.current_text),
+            false => next_word_end(&self.current_text, self.caret)
+        }
+    }
+
+    fn move_caret(&mut self, new_caret: usize, extend_selection: bool) {
+        if extend_selection {
+            if self.selection_anchor.is_none() {
+                self.selection_anchor = Some(self.caret);
+            }
+        } else {
+            self.selection_anchor = None;
+        }
+        self.caret = new_caret;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.get_selection() {
+            Some((start, end)) => {
+                self.remove_graphemes(start, end);
+                self.caret = start;
+                self.selection_anchor = None;
+                true
+            }, None => {
+                self.selection_anchor = None;
+                false
+            }
+        }
     }
 }
 
 impl ComponentBehavior for EditTextRenderController {
 
     fn attach(&mut self, agent: &mut dyn LayerAgent){
-        agent.claim_mouse_in_out_space(self.region.get_max_region());
-        agent.claim_render_space(self.region.get_max_region(), RenderTrigger::Request, 
-                determine_render_opacity(vec![self.base_colors, self.hover_colors, self.active_colors]), 
-                RenderPhase::Text).expect("Should have render space for EditTextRenderController");
-        agent.make_key_down_listener(10);
-        agent.claim_mouse_click_space(self.region.get_max_region()).expect("Should have click space for EditTextRenderController");
-        agent.make_copy_listener(50);
-        agent.make_paste_listener(50);
-        agent.make_cut_listener(50);
+        if let Err(error) = self.try_attach(agent) {
+            self.attach_error = Some(error);
+        }
     }
 
     fn set_agent(&mut self, agent: Weak<RefCell<ComponentAgent>>){
@@ -224,15 +858,23 @@ impl ComponentBehavior for EditTextRenderController {
     }
 
     fn render(&mut self, params: &mut RenderParams) -> BehaviorRenderResult {
-        let region = self.get_current_region();
+        if self.revealed_grapheme.is_some() && current_time() - self.reveal_time >= self.reveal_duration {
+            self.revealed_grapheme = None;
+            self.update_text();
+        }
+
         let actions = vec![PassedRenderAction::new(self.get_max_region())];
         let colors;
-        let result = match self.mouse_over {
+        let result = match self.mouse_over && self.enabled {
             true => BehaviorRenderResult::with_cursor(Cursor::TEXT, actions),
             false => BehaviorRenderResult::without_cursor(actions)
         };
         
-        if self.active {
+        if !self.enabled {
+            colors = self.disabled_colors;
+        } else if !self.is_valid() {
+            colors = self.error_colors;
+        } else if self.active {
             colors = self.active_colors;
         } else if self.mouse_over {
             colors = self.hover_colors;
@@ -240,24 +882,61 @@ impl ComponentBehavior for EditTextRenderController {
             colors = self.base_colors;
         }
 
-        if self.region.should_clear_remaining(&self.text_model, params) {
+        // The scroll offset can change between renders, so the old text must always be cleared when scrolling
+        if self.is_scrolling() || self.region.should_clear_remaining(&self.text_model, params) {
             self.text_model.get_font().fill_rect(self.region.get_max_region(), colors.background_color);
         }
 
-        let render_width = self.text_model.get_render_width(region.get_float_height());
-        let render_height;
-        if render_width <= region.get_float_width() {
-            render_height = region.get_float_height();
+        if let (true, false, Some(placeholder)) = (self.current_text.is_empty(), self.active, &self.placeholder) {
+
+            // The background has to match the state of the field, or the placeholder would stand out while hovering
+            let placeholder_colors = TextColors::new(self.placeholder_colors.fill_color, self.placeholder_colors.stroke_color, colors.background_color);
+            let placeholder_region = self.region.get_current_region(placeholder);
+            placeholder.render(placeholder_region.get_float_min_x(), placeholder_region.get_float_min_y(), 
+                    placeholder_region.get_float_height(), placeholder_colors);
+            return result;
+        }
+
+        self.update_scroll_offset();
+        let (text_x, text_y, render_height) = self.get_text_placement();
+        if self.is_scrolling() {
+            self.render_graphemes(0, count_graphemes(&self.display_text), false, colors);
         } else {
-            render_height = region.get_float_height() * region.get_float_width() / render_width;
+            self.text_model.render(text_x, text_y, render_height, colors);
+        }
+
+        if let (true, Some((start, end))) = (self.active, self.get_selection()) {
+
+            // Render the selected part again, on top of a highlighted background
+            let selected_colors = TextColors::new(colors.fill_color, colors.stroke_color, self.selection_color);
+            self.render_graphemes(start, end, true, selected_colors);
+        }
+
+        if self.composition.is_some() {
+
+            // Underline the text that is still being composed
+            let start_x = text_x + self.get_prefix_width(self.caret, render_height);
+            let end_x = text_x + self.get_prefix_width(self.caret + self.get_composition_length(), render_height);
+            self.text_model.get_font().fill_rect(Region::from_floats(start_x, text_y, end_x, text_y + render_height / 16.0), colors.fill_color);
         }
 
-        self.text_model.render(region.get_float_min_x(), region.get_float_min_y(), render_height, colors);
+        if self.active {
+            let caret_width = render_height / 16.0;
+            let mut caret_x = text_x + self.get_prefix_width(self.caret + self.get_composition_length(), render_height);
+
+            // Scrolling keeps the caret inside the field, but it could still stick out at the right edge
+            if self.is_scrolling() {
+                let max_region = self.get_max_region();
+                caret_x = caret_x.min(max_region.get_float_min_x() + max_region.get_float_width() - caret_width);
+            }
+            draw_blinking_caret(self.text_model.get_font(), Region::from_floats(caret_x, text_y, caret_x + caret_width, text_y + render_height), 
+                    colors.fill_color, self.caret_time, &self.agent);
+        }
         result
     }
 
     fn get_cursor(&mut self, _params: &mut CursorParams) -> Option<Cursor> {
-        if self.mouse_over {
+        if self.mouse_over && self.enabled {
             Some(Cursor::TEXT)
         } else {
             None
@@ -265,71 +944,190 @@ impl ComponentBehavior for EditTextRenderController {
     }
 
     fn mouse_move(&mut self, params: &mut MouseMoveParams) {
-        let region = self.get_max_region();
-        let new_mouse_over = params.new_mouse_pos.is_some() && region.is_float_inside(params.new_mouse_pos.unwrap());
+        self.last_mouse_pos = params.new_mouse_pos;
+        self.update_mouse_over();
+
+        if self.dragging {
+            if let Some(mouse_pos) = params.new_mouse_pos {
+                let new_caret = self.get_grapheme_at(mouse_pos.get_x());
+                if new_caret != self.caret {
+                    self.move_caret(new_caret, true);
+                    self.caret_time = current_time();
+                    self.request_render_if_attached();
+                }
+            }
+        }
+    }
 
-        if !self.active && self.mouse_over != new_mouse_over {
-            self.agent().borrow_mut().request_render();
+    fn mouse_down_inside(&mut self, params: &mut MouseDownParams) {
+        if self.active && self.enabled && params.down.get_button() == 0 {
+            self.history.break_merge();
+            self.caret = self.get_grapheme_at(params.mouse_pos.get_x());
+            self.selection_anchor = Some(self.caret);
+            self.caret_time = current_time();
+            self.dragging = true;
+            self.request_render_if_attached();
         }
-        self.mouse_over = new_mouse_over;
     }
 
-    fn mouse_click_inside(&mut self, _params: &mut MouseClickParams) {
-        self.active = !self.active;
-        self.agent().borrow_mut().request_render();
+    fn mouse_up(&mut self, _params: &mut MouseUpParams) {
+        self.dragging = false;
+    }
+
+    fn mouse_click_inside(&mut self, params: &mut MouseClickParams) {
+        if !self.enabled {
+            return;
+        }
+
+        let click_time = current_time();
+        if self.active && click_time - self.last_click_time <= MULTI_CLICK_INTERVAL {
+            self.click_count = self.click_count.saturating_add(1);
+        } else {
+            self.click_count = 1;
+        }
+        self.last_click_time = click_time;
+
+        let clicked_grapheme = self.get_grapheme_at(params.mouse_pos.get_x());
+        match self.click_count {
+            1 => if self.get_selection().is_none() {
+
+                // Don't throw away the selection the user just made by dragging
+                self.move_caret(clicked_grapheme, false);
+            },
+            2 if !self.is_masked() => {
+                let (word_start, word_end) = word_bounds(&self.current_text, clicked_grapheme);
+                self.selection_anchor = Some(word_start);
+                self.caret = word_end;
+            },
+            _ => {
+                self.selection_anchor = Some(0);
+                self.caret = count_graphemes(&self.current_text);
+            }
+        }
+
+        self.history.break_merge();
+        if let Some(focus_handle) = &self.focus_handle {
+            focus_handle.claim_focus();
+        }
+        self.set_active(true);
+        self.caret_time = click_time;
+        self.request_render_if_attached();
     }
 
     fn mouse_click_outside(&mut self, _params: &mut MouseClickOutParams) {
-        self.active = false;
-        self.agent().borrow_mut().request_render();
+        self.set_active(false);
+        self.selection_anchor = None;
+        self.request_render_if_attached();
     }
 
     fn key_down(&mut self, params: &mut KeyDownParams) -> bool {
-        if self.is_active() && !params.keys.is_control_down() {
-            let key = params.keys.get_key();
-            let mut char_counter = 0;
-            {
-                let mut chars = UnicodeSegmentation::graphemes(key, true);
-                let mut next_char = chars.next();
-                while next_char.is_some() {
-                    char_counter += 1;
-                    next_char = chars.next();
-                }
+        if !self.is_active() {
+            return false;
+        }
+
+        // While composing, the keys belong to the input method
+        if self.is_composing() {
+            return true;
+        }
+
+        let key = params.keys.get_key();
+        if key == "Tab" {
+
+            // Leave focus traversal to the focus manager
+            return false;
+        }
+        let shift = params.keys.is_shift_down();
+        let length = count_graphemes(&self.current_text);
+        let previous_state = self.get_edit_state();
+        let mut edit_kind = EditKind::Other;
+        let mut submitted = false;
+        let mut typed = false;
+        self.revealed_grapheme = None;
+        if params.keys.is_control_down() {
+            match key {
+                "a" | "A" => {
+                    self.selection_anchor = Some(0);
+                    self.caret = length;
+                },
+                "z" | "Z" => {
+                    match shift {
+                        true => self.redo(),
+                        false => self.undo()
+                    };
+                    return true;
+                },
+                "y" | "Y" => {
+                    self.redo();
+                    return true;
+                },
+                "ArrowLeft" => self.move_caret(self.get_previous_word_start(), shift),
+                "ArrowRight" => self.move_caret(self.get_next_word_end(), shift),
+                "Backspace" => if !self.delete_selection() {
+                    let word_start = self.get_previous_word_start();
+                    self.remove_graphemes(word_start, self.caret);
+                    self.caret = word_start;
+                },
+                "Delete" => if !self.delete_selection() {
+                    self.remove_graphemes(self.caret, self.get_next_word_end());
+                },
+
+                // Leave the other shortcuts (like copy and paste) to the browser
+                _ => return false
             }
-            if char_counter < 3 {
-                self.current_text += &key;
-            } else {
-                if key == "Backspace" {
-                    let mut chars = UnicodeSegmentation::graphemes(self.current_text.as_str(), true);
-                    let mut new_text = "".to_string();
-                    let mut maybe_current_char = chars.next();
-                    while maybe_current_char.is_some() {
-                        let next_char = chars.next();
-                        let current_char = maybe_current_char.unwrap();
-                        if next_char.is_some() {
-                            new_text += current_char;
-                        }
-                        maybe_current_char = next_char;
-                    }
-                    self.current_text = new_text;
-                } else if key == "Escape" || key == "Enter" {
+        } else if is_typed_key(key) {
+            self.delete_selection();
+            self.insert_at_caret(key);
+            edit_kind = EditKind::Typing;
+            typed = true;
+        } else {
+            match key {
+                "Backspace" if !self.delete_selection() && self.caret > 0 => {
+                    self.remove_graphemes(self.caret - 1, self.caret);
+                    self.caret -= 1;
+                },
+                "Delete" if !self.delete_selection() => {
+                    self.remove_graphemes(self.caret, self.caret + 1);
+                },
+                "ArrowLeft" => match (self.get_selection(), shift) {
+                    (Some((start, _)), false) => self.move_caret(start, false),
+                    _ => self.move_caret(self.caret.max(1) - 1, shift)
+                },
+                "ArrowRight" => match (self.get_selection(), shift) {
+                    (Some((_, end)), false) => self.move_caret(end, false),
+                    _ => self.move_caret((self.caret + 1).min(length), shift)
+                },
+                "Home" => self.move_caret(0, shift),
+                "End" => self.move_caret(length, shift),
+                "Escape" => {
+                    self.selection_anchor = None;
                     self.set_active(false);
-                }
+                },
+                "Enter" => {
+                    self.selection_anchor = None;
+                    self.set_active(false);
+                    submitted = true;
+                },
+                _ => {}
             }
+        }
 
-            self.update_text();
-            true
-        } else {
-            false
+        // Rejected keystrokes are rolled back, so there would be nothing new to reveal
+        if self.record_edit(previous_state, edit_kind) && typed && self.reveal_duration > 0.0 && self.caret > 0 {
+            self.revealed_grapheme = Some(self.caret - 1);
+            self.reveal_time = current_time();
         }
+        self.caret_time = current_time();
+        self.update_text();
+        if submitted {
+            self.fire_submit();
+        }
+        true
     }
 
     fn on_copy(&mut self) -> Option<ClipboardData> {
-        match self.is_active() {
-            true => match self.current_text.is_empty() {
-                false => Some(ClipboardData::Text(self.current_text.clone())),
-                true => None
-            }, false => None
+        match self.is_active() && !self.is_masked() {
+            true => self.get_selected_text().map(|selected| ClipboardData::Text(selected.to_string())),
+            false => None
         }
     }
 
@@ -337,7 +1135,11 @@ impl ComponentBehavior for EditTextRenderController {
         match self.is_active() {
             true => match clipboard {
                 ClipboardData::Text(text_to_paste) => {
-                    self.current_text += text_to_paste;
+                    let previous_state = self.get_edit_state();
+                    self.delete_selection();
+                    self.insert_at_caret(text_to_paste);
+                    self.record_edit(previous_state, EditKind::Other);
+                    self.caret_time = current_time();
                     self.update_text();
                     true
                 }
@@ -346,14 +1148,18 @@ impl ComponentBehavior for EditTextRenderController {
     }
 
     fn on_cut(&mut self) -> Option<ClipboardData> {
-        match self.is_active() {
-            true => match self.current_text.is_empty() {
-                false => {
-                    let result = Some(ClipboardData::Text(self.current_text.clone()));
-                    self.current_text = "".to_string();
-                    self.update_text();
-                    result
-                }, true => None
+        match self.is_active() && !self.is_masked() {
+            true => {
+                let result = self.get_selected_text().map(|selected| ClipboardData::Text(selected.to_string()));
+                let previous_state = self.get_edit_state();
+                self.delete_selection();
+                match self.record_edit(previous_state, EditKind::Other) {
+                    true => {
+                        self.caret_time = current_time();
+                        self.update_text();
+                        result
+                    }, false => None
+                }
             }, false => None
         }
     }
@@ -369,13 +1175,179 @@ impl TextRenderController for EditTextRenderController {
         self.region.get_current_region(&self.text_model)
     }
 
+    fn set_region(&mut self, new_region: Region) -> Result<(), ComponentError> {
+        let old_region = self.region.get_max_region();
+
+        // Only attached and visible controllers have claimed spaces that need to be moved
+        let claimed = match self.render_opacity.filter(|_| self.visible) {
+            Some(render_opacity) => Some((render_opacity, upgrade_agent(&self.agent, "EditTextRenderController")?)),
+            None => None
+        };
+        self.region.set_region(new_region)?;
+        if let Some((render_opacity, agent_cell)) = claimed {
+            let mut agent = agent_cell.borrow_mut();
+            if let Err(error) = reclaim_render_space(&mut agent, old_region, new_region, render_opacity, "EditTextRenderController") {
+                self.region.set_region(old_region).expect("Should be able to move back to the old region");
+                return Err(error);
+            }
+            if let Err(error) = reclaim_click_space(&mut agent, old_region, new_region, "EditTextRenderController") {
+                reclaim_render_space(&mut agent, new_region, old_region, render_opacity, "EditTextRenderController")
+                        .expect("Should be able to claim the released render space again");
+                self.region.set_region(old_region).expect("Should be able to move back to the old region");
+                return Err(error);
+            }
+            agent.release_mouse_in_out_space(old_region);
+            agent.claim_mouse_in_out_space(new_region);
+            agent.request_render();
+        }
+        self.scroll_start = 0;
+        self.dragging = false;
+        self.update_mouse_over();
+        Ok(())
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) -> Result<(), ComponentError> {
+        if self.visible == visible {
+            return Ok(());
+        }
+
+        // Only attached controllers have claims to give up or restore
+        if self.render_opacity.is_some() {
+            let region = self.region.get_max_region();
+            let all_colors = self.get_all_colors();
+            let agent_cell = upgrade_agent(&self.agent, "EditTextRenderController")?;
+            let mut agent = agent_cell.borrow_mut();
+            set_render_space_visible(&mut agent, region, visible, all_colors, &mut self.render_opacity, "EditTextRenderController")?;
+            if visible {
+                if agent.claim_mouse_click_space(region).is_err() {
+                    agent.release_render_space(region);
+                    return Err(ComponentError::ClickSpaceTaken("EditTextRenderController"));
+                }
+                agent.claim_mouse_in_out_space(region);
+                agent.make_key_down_listener(10);
+                agent.make_copy_listener(50);
+                agent.make_paste_listener(50);
+                agent.make_cut_listener(50);
+            } else {
+                agent.release_mouse_in_out_space(region);
+                agent.remove_key_down_listener();
+                agent.release_mouse_click_space(region);
+                agent.remove_copy_listener();
+                agent.remove_paste_listener();
+                agent.remove_cut_listener();
+            }
+            agent.request_render();
+        }
+        if !visible {
+            self.set_active(false);
+            self.selection_anchor = None;
+            self.dragging = false;
+            self.mouse_over = false;
+        }
+        self.visible = visible;
+        if visible {
+            self.update_mouse_over();
+        }
+        Ok(())
+    }
+
+    fn take_attach_error(&mut self) -> Option<ComponentError> {
+        self.attach_error.take()
+    }
+
     fn set_text(&mut self, new_text: &str){
         self.current_text = new_text.to_string();
+        self.caret = count_graphemes(new_text);
+        self.selection_anchor = None;
+        self.history.clear();
         self.update_text();
     }
 
     fn set_text_model(&mut self, new_text: TextModel){
         self.text_model = new_text;
-        self.agent().borrow_mut().request_render();
+        self.visible_model = None;
+        self.selected_model = None;
+        self.request_render_if_attached();
+    }
+
+    fn is_focused(&self) -> bool {
+        self.active
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.set_active(false);
+            self.selection_anchor = None;
+            self.dragging = false;
+        }
+        self.request_render_if_attached();
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.set_active(focused);
+        if !focused {
+            self.selection_anchor = None;
+        }
+        self.caret_time = current_time();
+        self.request_render_if_attached();
+    }
+
+    fn set_focus_handle(&mut self, handle: Option<FocusHandle>) {
+        self.focus_handle = handle;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn word_bounds_cover_the_word_at_the_index() {
+        assert_eq!((0, 5), word_bounds("hello world", 0));
+        assert_eq!((0, 5), word_bounds("hello world", 4));
+        assert_eq!((5, 6), word_bounds("hello world", 5));
+        assert_eq!((6, 11), word_bounds("hello world", 8));
+    }
+
+    #[test]
+    fn word_bounds_past_the_end_use_the_last_word() {
+        assert_eq!((6, 11), word_bounds("hello world", 11));
+        assert_eq!((6, 11), word_bounds("hello world", 20));
+        assert_eq!((0, 0), word_bounds("", 0));
+    }
+
+    #[test]
+    fn previous_word_start_skips_whitespace() {
+        assert_eq!(6, previous_word_start("hello world", 11));
+        assert_eq!(6, previous_word_start("hello world", 8));
+        assert_eq!(0, previous_word_start("hello world", 6));
+        assert_eq!(0, previous_word_start("hello world", 0));
+        assert_eq!(0, previous_word_start("", 0));
+    }
+
+    #[test]
+    fn next_word_end_skips_whitespace() {
+        assert_eq!(5, next_word_end("hello world", 0));
+        assert_eq!(11, next_word_end("hello world", 5));
+        assert_eq!(11, next_word_end("hello world", 11));
+        assert_eq!(0, next_word_end("", 0));
+    }
+
+    #[test]
+    fn word_helpers_count_graphemes() {
+        let text = "cafe\u{301} au lait";
+        assert_eq!((0, 4), word_bounds(text, 2));
+        assert_eq!(4, next_word_end(text, 0));
+        assert_eq!(5, previous_word_start(text, 7));
     }
 }
\ No newline at end of file
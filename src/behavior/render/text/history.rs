@@ -0,0 +1,176 @@
+#[derive(Clone,Copy,PartialEq,Eq)]
+pub(crate) enum EditKind {
+
+    Typing,
+    Other
+}
+
+#[derive(Clone)]
+pub(crate) struct EditState {
+
+    pub text: String,
+    pub caret: usize,
+    pub selection_anchor: Option<usize>
+}
+
+pub(crate) struct EditHistory {
+
+    undo_states: Vec<EditState>,
+    redo_states: Vec<EditState>,
+
+    last_kind: Option<EditKind>
+}
+
+const MAX_HISTORY_SIZE: usize = 100;
+
+impl EditHistory {
+
+    pub fn new() -> EditHistory {
+        EditHistory {
+            undo_states: Vec::new(),
+            redo_states: Vec::new(),
+
+            last_kind: None
+        }
+    }
+
+    pub fn record(&mut self, previous_state: EditState, kind: EditKind) {
+
+        // Consecutive typing is merged into a single step
+        if kind != EditKind::Typing || self.last_kind != Some(EditKind::Typing) {
+            self.undo_states.push(previous_state);
+            if self.undo_states.len() > MAX_HISTORY_SIZE {
+                self.undo_states.remove(0);
+            }
+        }
+        self.redo_states.clear();
+        self.last_kind = Some(kind);
+    }
+
+    pub fn break_merge(&mut self) {
+        self.last_kind = None;
+    }
+
+    pub fn undo(&mut self, current_state: EditState) -> Option<EditState> {
+        self.last_kind = None;
+        let result = self.undo_states.pop();
+        if result.is_some() {
+            self.redo_states.push(current_state);
+        }
+        result
+    }
+
+    pub fn redo(&mut self, current_state: EditState) -> Option<EditState> {
+        self.last_kind = None;
+        let result = self.redo_states.pop();
+        if result.is_some() {
+            self.undo_states.push(current_state);
+        }
+        result
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_states.clear();
+        self.redo_states.clear();
+        self.last_kind = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn state(text: &str) -> EditState {
+        EditState {
+            text: text.to_string(),
+            caret: text.len(),
+            selection_anchor: None
+        }
+    }
+
+    #[test]
+    fn consecutive_typing_is_merged() {
+        let mut history = EditHistory::new();
+        history.record(state(""), EditKind::Typing);
+        history.record(state("a"), EditKind::Typing);
+        history.record(state("ab"), EditKind::Typing);
+
+        assert_eq!("", history.undo(state("abc")).unwrap().text);
+        assert!(history.undo(state("")).is_none());
+    }
+
+    #[test]
+    fn other_edits_are_not_merged() {
+        let mut history = EditHistory::new();
+        history.record(state(""), EditKind::Typing);
+        history.record(state("a"), EditKind::Other);
+        history.record(state("ab"), EditKind::Other);
+
+        assert_eq!("ab", history.undo(state("abc")).unwrap().text);
+        assert_eq!("a", history.undo(state("ab")).unwrap().text);
+        assert_eq!("", history.undo(state("a")).unwrap().text);
+    }
+
+    #[test]
+    fn break_merge_starts_a_new_step() {
+        let mut history = EditHistory::new();
+        history.record(state(""), EditKind::Typing);
+        history.record(state("a"), EditKind::Typing);
+        history.break_merge();
+        history.record(state("ab"), EditKind::Typing);
+        history.record(state("abc"), EditKind::Typing);
+
+        assert_eq!("ab", history.undo(state("abcd")).unwrap().text);
+        assert_eq!("", history.undo(state("ab")).unwrap().text);
+    }
+
+    #[test]
+    fn typing_after_undo_is_not_merged() {
+        let mut history = EditHistory::new();
+        history.record(state(""), EditKind::Typing);
+        assert_eq!("", history.undo(state("a")).unwrap().text);
+        history.record(state(""), EditKind::Typing);
+        history.record(state("b"), EditKind::Typing);
+
+        assert_eq!("", history.undo(state("bc")).unwrap().text);
+    }
+
+    #[test]
+    fn redo_restores_undone_states() {
+        let mut history = EditHistory::new();
+        history.record(state(""), EditKind::Other);
+        history.record(state("a"), EditKind::Other);
+
+        assert_eq!("a", history.undo(state("ab")).unwrap().text);
+        assert_eq!("ab", history.redo(state("a")).unwrap().text);
+        assert!(history.redo(state("ab")).is_none());
+    }
+
+    #[test]
+    fn recording_clears_redo() {
+        let mut history = EditHistory::new();
+        history.record(state(""), EditKind::Other);
+        history.undo(state("a"));
+        history.record(state(""), EditKind::Other);
+
+        assert!(history.redo(state("b")).is_none());
+    }
+
+    #[test]
+    fn history_size_is_limited() {
+        let mut history = EditHistory::new();
+        for index in 0..MAX_HISTORY_SIZE + 10 {
+            history.record(state(&index.to_string()), EditKind::Other);
+        }
+
+        let mut undo_count = 0;
+        let mut oldest = String::new();
+        while let Some(previous) = history.undo(state("current")) {
+            oldest = previous.text;
+            undo_count += 1;
+        }
+        assert_eq!(MAX_HISTORY_SIZE, undo_count);
+        assert_eq!("10", oldest);
+    }
+}
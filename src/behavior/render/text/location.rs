@@ -1,15 +1,25 @@
 use wasmuri_container::RenderParams;
 use wasmuri_core::Region;
 
+use crate::ComponentError;
+
 use wasmuri_text::TextModel;
 
 pub trait TextLocationProperties {
 
     fn get_max_region(&self) -> Region;
 
+    fn set_region(&mut self, _new_region: Region) -> Result<(), ComponentError> {
+        Err(ComponentError::RegionFixed("TextLocationProperties"))
+    }
+
     fn get_current_region(&self, text: &TextModel) -> Region;
 
     fn should_clear_remaining(&self, text: &TextModel, params: &mut RenderParams) -> bool;
+
+    fn needs_scroll_offset(&self, _text: &TextModel) -> bool {
+        false
+    }
 }
 
 pub type TextRegionProps = Box<dyn TextLocationProperties>;
@@ -52,6 +62,11 @@ impl TextLocationProperties for AlignedTextLocation {
         self.max_region
     }
 
+    fn set_region(&mut self, new_region: Region) -> Result<(), ComponentError> {
+        self.max_region = new_region;
+        Ok(())
+    }
+
     fn get_current_region(&self, text: &TextModel) -> Region {
         let preferred_scale_y = self.max_region.get_float_height();
         let preferred_scale_x = text.get_render_width(preferred_scale_y);
@@ -87,6 +102,10 @@ impl TextLocationProperties for AlignedTextLocation {
     fn should_clear_remaining(&self, _text: &TextModel, _params: &mut RenderParams) -> bool {
         self.clear_remaining
     }
+
+    fn needs_scroll_offset(&self, text: &TextModel) -> bool {
+        text.get_render_width(self.max_region.get_float_height()) > self.max_region.get_float_width()
+    }
 }
 
 pub fn label_location(max_region: Region, alignment: TextAlignment) -> TextRegionProps {
@@ -1,18 +1,30 @@
 mod simple;
 mod button;
 mod edit;
+mod area;
 mod location;
+mod validate;
+mod history;
 
 pub use simple::*;
 pub use button::*;
 pub use edit::*;
+pub use area::*;
 pub use location::*;
+pub use validate::*;
 
+use std::cell::RefCell;
 use std::rc::*;
 
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::ComponentError;
+use crate::behavior::FocusHandle;
+use crate::time::current_time;
+
 use wasmuri_container::*;
 use wasmuri_core::*;
-use wasmuri_text::TextModel;
+use wasmuri_text::{Font, TextModel};
 
 pub trait TextRenderController {
 
@@ -23,6 +35,40 @@ pub trait TextRenderController {
     fn get_max_region(&self) -> Region;
 
     fn get_current_region(&self) -> Region;
+
+    fn set_region(&mut self, _new_region: Region) -> Result<(), ComponentError> {
+        Err(ComponentError::RegionFixed("TextRenderController"))
+    }
+
+    fn is_visible(&self) -> bool {
+        true
+    }
+
+    fn set_visible(&mut self, _visible: bool) -> Result<(), ComponentError> {
+        Ok(())
+    }
+
+    fn take_attach_error(&mut self) -> Option<ComponentError> {
+        None
+    }
+
+    fn is_focused(&self) -> bool {
+        false
+    }
+
+    fn set_focused(&mut self, _focused: bool) {}
+
+    fn set_focus_handle(&mut self, _handle: Option<FocusHandle>) {}
+
+    fn set_pressed(&mut self, _pressed: bool) {}
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    fn set_enabled(&mut self, _enabled: bool) {}
+
+    fn set_checked(&mut self, _checked: bool) {}
 }
 
 fn determine_render_opacity(colors: Vec<TextColors>) -> RenderOpacity {
@@ -52,4 +98,176 @@ fn determine_render_opacity(colors: Vec<TextColors>) -> RenderOpacity {
     } else {
         RenderOpacity::DynamicSolidOrNothing
     }
+}
+
+// When the new render space is taken, the old one will be claimed again
+// The layer renders whatever is behind a released region again, so moved components don't need to clear their old region
+fn reclaim_render_space(agent: &mut ComponentAgent, old_region: Region, new_region: Region, opacity: RenderOpacity, 
+        owner: &'static str) -> Result<(), ComponentError> {
+    agent.release_render_space(old_region);
+    if agent.claim_render_space(new_region, RenderTrigger::Request, opacity, RenderPhase::Text).is_err() {
+        agent.claim_render_space(old_region, RenderTrigger::Request, opacity, RenderPhase::Text)
+                .expect("Should be able to claim the released render space again");
+        return Err(ComponentError::RenderSpaceTaken(owner));
+    }
+    Ok(())
+}
+
+// Hidden controllers give up their render space, so the layer renders whatever is behind them instead of their old text
+fn set_render_space_visible(agent: &mut ComponentAgent, region: Region, visible: bool, colors: Vec<TextColors>, 
+        claimed_opacity: &mut Option<RenderOpacity>, owner: &'static str) -> Result<(), ComponentError> {
+    if visible {
+        let opacity = determine_render_opacity(colors);
+        agent.claim_render_space(region, RenderTrigger::Request, opacity, RenderPhase::Text).map_err(|_| ComponentError::RenderSpaceTaken(owner))?;
+        *claimed_opacity = Some(opacity);
+    } else {
+        agent.release_render_space(region);
+    }
+    Ok(())
+}
+
+// The layer decides how to draw behind a render space based on its opacity, so it has to be claimed again when that changes
+fn update_render_opacity(agent: &mut ComponentAgent, region: Region, claimed_opacity: &mut Option<RenderOpacity>, 
+        colors: Vec<TextColors>, owner: &'static str) {
+    if let Some(old_opacity) = *claimed_opacity {
+        let new_opacity = determine_render_opacity(colors);
+
+        // When the layer refuses the new opacity, the render space stays claimed with the old one
+        if new_opacity != old_opacity && reclaim_render_space(agent, region, region, new_opacity, owner).is_ok() {
+            *claimed_opacity = Some(new_opacity);
+        }
+    }
+}
+
+fn lighten_component(component: u8) -> u8 {
+    ((component as u16 + 255) / 2) as u8
+}
+
+fn lighten_color(color: Color) -> Color {
+    Color::from_rgba(lighten_component(color.get_red()), lighten_component(color.get_green()), lighten_component(color.get_blue()), lighten_component(color.get_alpha()))
+}
+
+fn to_active_colors(colors: TextColors) -> TextColors {
+    TextColors::new(colors.fill_color, colors.stroke_color, lighten_color(colors.background_color))
+}
+
+fn darken_component(component: u8) -> u8 {
+    ((component as u16 * 19) / 20) as u8
+}
+
+fn darken_color(color: Color) -> Color {
+    Color::from_rgba(darken_component(color.get_red()), darken_component(color.get_green()), darken_component(color.get_blue()), color.get_alpha())
+}
+
+fn to_hover_colors(colors: TextColors) -> TextColors {
+    TextColors::new(colors.fill_color, colors.stroke_color, darken_color(colors.background_color))
+}
+
+fn disable_component(component: u8) -> u8 {
+    ((component as u16 + 160 * 2) / 3) as u8
+}
+
+fn disable_color(color: Color) -> Color {
+    Color::from_rgba(disable_component(color.get_red()), disable_component(color.get_green()), disable_component(color.get_blue()), color.get_alpha())
+}
+
+fn to_disabled_colors(colors: TextColors) -> TextColors {
+    TextColors::new(disable_color(colors.fill_color), disable_color(colors.stroke_color), disable_color(colors.background_color))
+}
+
+fn count_graphemes(text: &str) -> usize {
+    UnicodeSegmentation::graphemes(text, true).count()
+}
+
+fn grapheme_offset(text: &str, grapheme_index: usize) -> usize {
+    match UnicodeSegmentation::grapheme_indices(text, true).nth(grapheme_index) {
+        Some((offset, _)) => offset,
+        None => text.len()
+    }
+}
+
+// Typed characters are always a single grapheme, while named keys like "Enter" or "F1" are longer
+fn is_typed_key(key: &str) -> bool {
+    count_graphemes(key) == 1
+}
+
+// Render widths grow linearly with the render height, so measuring at height 1 is enough for every height
+fn measure_prefix_widths(font: &Rc<Font>, text: &str) -> Vec<f32> {
+    let mut prefix_widths = Vec::with_capacity(text.len() + 1);
+    let mut width = 0.0;
+    prefix_widths.push(width);
+    for grapheme in UnicodeSegmentation::graphemes(text, true) {
+        width += Rc::clone(font).create_text_model(grapheme).get_render_width(1.0);
+        prefix_widths.push(width);
+    }
+    prefix_widths
+}
+
+const CARET_BLINK_INTERVAL: f64 = 500.0;
+
+// Keep rendering while active to make the caret blink
+fn draw_blinking_caret(font: &Font, caret_region: Region, color: Color, caret_time: f64, agent: &Option<Weak<RefCell<ComponentAgent>>>) {
+    if (((current_time() - caret_time) / CARET_BLINK_INTERVAL) as u64).is_multiple_of(2) {
+        font.fill_rect(caret_region, color);
+    }
+    if let Some(agent) = agent.as_ref().and_then(|agent| agent.upgrade()) {
+        agent.borrow_mut().request_render();
+    }
+}
+
+// Pick the grapheme boundary that is closest to x, get_width gives the width from start to a boundary
+fn get_closest_boundary(x: f32, start: usize, end: usize, get_width: impl Fn(usize) -> f32) -> usize {
+    let mut previous_width = 0.0;
+    for boundary in start + 1..=end {
+        let width = get_width(boundary);
+        if x < (previous_width + width) / 2.0 {
+            return boundary - 1;
+        }
+        previous_width = width;
+    }
+    end
+}
+
+// The inserted text could merge with the graphemes around it, so count again
+fn get_caret_after_insert(text: &str, insert_end: usize) -> usize {
+    count_graphemes(&text[..insert_end])
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn count_graphemes_counts_combined_characters_once() {
+        assert_eq!(0, count_graphemes(""));
+        assert_eq!(5, count_graphemes("hello"));
+        assert_eq!(4, count_graphemes("cafe\u{301}"));
+        assert_eq!(1, count_graphemes("\r\n"));
+    }
+
+    #[test]
+    fn grapheme_offset_returns_byte_offsets() {
+        let text = "e\u{301}a\u{e9}";
+        assert_eq!(0, grapheme_offset(text, 0));
+        assert_eq!(3, grapheme_offset(text, 1));
+        assert_eq!(4, grapheme_offset(text, 2));
+    }
+
+    #[test]
+    fn grapheme_offset_past_the_end_is_the_text_length() {
+        assert_eq!(5, grapheme_offset("hello", 5));
+        assert_eq!(5, grapheme_offset("hello", 9));
+        assert_eq!(0, grapheme_offset("", 0));
+    }
+
+    #[test]
+    fn is_typed_key_rejects_named_keys() {
+        assert!(is_typed_key("a"));
+        assert!(is_typed_key(" "));
+        assert!(is_typed_key("e\u{301}"));
+        assert!(!is_typed_key("F1"));
+        assert!(!is_typed_key("Enter"));
+        assert!(!is_typed_key(""));
+    }
 }
\ No newline at end of file
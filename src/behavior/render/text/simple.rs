@@ -7,6 +7,8 @@ use wasmuri_text::*;
 
 use super::*;
 
+use crate::{ComponentError, upgrade_agent};
+
 pub struct SimpleTextRenderController {
 
     region: TextRegionProps,
@@ -14,7 +16,10 @@ pub struct SimpleTextRenderController {
 
     text_model: TextModel,
 
-    colors: TextColors
+    colors: TextColors,
+    render_opacity: Option<RenderOpacity>,
+    visible: bool,
+    attach_error: Option<ComponentError>
 }
 
 impl SimpleTextRenderController {
@@ -25,7 +30,10 @@ impl SimpleTextRenderController {
             agent: None,
             text_model: Rc::clone(font).create_text_model(text),
 
-            colors
+            colors,
+            render_opacity: None,
+            visible: true,
+            attach_error: None
         }
     }
 
@@ -38,34 +46,61 @@ impl SimpleTextRenderController {
         (Rc::clone(&instance) as Rc<RefCell<dyn ComponentBehavior>>, instance)
     }
 
-    // TODO Update render opacity if necessary!
-
     pub fn set_fill_color(&mut self, new_color: Color, agent: &mut ComponentAgent){
         self.colors.fill_color = new_color;
+        self.update_render_opacity(agent);
         agent.request_render();
     }
 
     pub fn set_stroke_color(&mut self, new_color: Color, agent: &mut ComponentAgent){
         self.colors.stroke_color = new_color;
+        self.update_render_opacity(agent);
         agent.request_render();
     }
 
     pub fn set_background_color(&mut self, new_color: Color, agent: &mut ComponentAgent){
         self.colors.background_color = new_color;
+        self.update_render_opacity(agent);
         agent.request_render();
     }
 
     pub fn set_colors(&mut self, new_colors: TextColors, agent: &mut ComponentAgent){
         self.colors = new_colors;
+        self.update_render_opacity(agent);
         agent.request_render();
     }
+
+    pub fn try_attach(&mut self, agent: &mut dyn LayerAgent) -> Result<(), ComponentError> {
+        let render_opacity = determine_render_opacity(vec![self.colors]);
+
+        // Hidden controllers claim their render space when they are shown
+        if self.visible {
+            agent.claim_render_space(self.region.get_max_region(), RenderTrigger::Request, render_opacity, RenderPhase::Text)
+                    .map_err(|_| ComponentError::RenderSpaceTaken("SimpleTextRenderController"))?;
+        }
+        self.render_opacity = Some(render_opacity);
+        Ok(())
+    }
+
+    fn request_render_if_attached(&self){
+        if let Some(agent) = self.agent.as_ref().and_then(|agent| agent.upgrade()) {
+            agent.borrow_mut().request_render();
+        }
+    }
+
+    fn update_render_opacity(&mut self, agent: &mut ComponentAgent){
+        if self.visible {
+            update_render_opacity(agent, self.region.get_max_region(), &mut self.render_opacity, vec![self.colors], "SimpleTextRenderController");
+        }
+    }
 }
 
 impl ComponentBehavior for SimpleTextRenderController {
 
     fn attach(&mut self, agent: &mut dyn LayerAgent){
-        agent.claim_render_space(self.region.get_max_region(), RenderTrigger::Request, determine_render_opacity(vec![self.colors]), 
-                RenderPhase::Text).expect("Should have render space for SimpleTextRenderHelper");
+        if let Err(error) = self.try_attach(agent) {
+            self.attach_error = Some(error);
+        }
     }
 
     fn set_agent(&mut self, agent: Weak<RefCell<ComponentAgent>>){
@@ -100,13 +135,57 @@ impl TextRenderController for SimpleTextRenderController {
         self.region.get_current_region(&self.text_model)
     }
 
+    fn set_region(&mut self, new_region: Region) -> Result<(), ComponentError> {
+        let old_region = self.region.get_max_region();
+
+        // Only attached and visible controllers have claimed a render space
+        let claimed = match self.render_opacity.filter(|_| self.visible) {
+            Some(render_opacity) => Some((render_opacity, upgrade_agent(&self.agent, "SimpleTextRenderController")?)),
+            None => None
+        };
+        self.region.set_region(new_region)?;
+        if let Some((render_opacity, agent_cell)) = claimed {
+            let mut agent = agent_cell.borrow_mut();
+            if let Err(error) = reclaim_render_space(&mut agent, old_region, new_region, render_opacity, "SimpleTextRenderController") {
+                self.region.set_region(old_region).expect("Should be able to move back to the old region");
+                return Err(error);
+            }
+            agent.request_render();
+        }
+        Ok(())
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, visible: bool) -> Result<(), ComponentError> {
+        if self.visible == visible {
+            return Ok(());
+        }
+
+        // Only attached controllers have a render space to give up or claim
+        if self.render_opacity.is_some() {
+            let agent_cell = upgrade_agent(&self.agent, "SimpleTextRenderController")?;
+            let mut agent = agent_cell.borrow_mut();
+            set_render_space_visible(&mut agent, self.region.get_max_region(), visible, vec![self.colors], &mut self.render_opacity, "SimpleTextRenderController")?;
+            agent.request_render();
+        }
+        self.visible = visible;
+        Ok(())
+    }
+
+    fn take_attach_error(&mut self) -> Option<ComponentError> {
+        self.attach_error.take()
+    }
+
     fn set_text(&mut self, new_text: &str){
         self.text_model = Rc::clone(self.text_model.get_font()).create_text_model(new_text);
-        self.agent.as_ref().expect("Agent should have been set by now").upgrade().expect("Component agent should not have been dropped").borrow_mut().request_render();
+        self.request_render_if_attached();
     }
 
     fn set_text_model(&mut self, new_text: TextModel){
         self.text_model = new_text;
-        self.agent.as_ref().expect("Agent should have been set by now").upgrade().expect("Component agent should not have been dropped").borrow_mut().request_render();
+        self.request_render_if_attached();
     }
 }
\ No newline at end of file
@@ -0,0 +1,225 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+pub trait TextValidator {
+
+    fn accepts_input(&self, old_text: &str, new_text: &str) -> bool;
+
+    fn is_valid(&self, text: &str) -> bool;
+}
+
+pub struct NumericValidator {
+
+    allow_negative: bool
+}
+
+impl NumericValidator {
+
+    pub fn new(allow_negative: bool) -> NumericValidator {
+        NumericValidator {
+            allow_negative
+        }
+    }
+}
+
+impl TextValidator for NumericValidator {
+
+    fn accepts_input(&self, _old_text: &str, new_text: &str) -> bool {
+        let digits = match self.allow_negative && new_text.starts_with('-') {
+            true => &new_text[1..],
+            false => new_text
+        };
+        digits.chars().all(|character| character.is_ascii_digit())
+    }
+
+    // An empty field hasn't been filled in yet, which shouldn't be shown as an error
+    fn is_valid(&self, text: &str) -> bool {
+        self.accepts_input(text, text) && text != "-"
+    }
+}
+
+pub struct MaxLengthValidator {
+
+    max_length: usize
+}
+
+impl MaxLengthValidator {
+
+    pub fn new(max_length: usize) -> MaxLengthValidator {
+        MaxLengthValidator {
+            max_length
+        }
+    }
+}
+
+impl TextValidator for MaxLengthValidator {
+
+    fn accepts_input(&self, old_text: &str, new_text: &str) -> bool {
+        let new_length = UnicodeSegmentation::graphemes(new_text, true).count();
+
+        // Don't prevent the user from shortening text that was already too long
+        new_length <= self.max_length || new_length < UnicodeSegmentation::graphemes(old_text, true).count()
+    }
+
+    fn is_valid(&self, text: &str) -> bool {
+        UnicodeSegmentation::graphemes(text, true).count() <= self.max_length
+    }
+}
+
+pub struct PredicateValidator {
+
+    accept_input: Box<dyn Fn(&str) -> bool>,
+    valid: Box<dyn Fn(&str) -> bool>
+}
+
+impl PredicateValidator {
+
+    pub fn new(accept_input: Box<dyn Fn(&str) -> bool>, valid: Box<dyn Fn(&str) -> bool>) -> PredicateValidator {
+        PredicateValidator {
+            accept_input,
+            valid
+        }
+    }
+
+    pub fn input_only(accept_input: Box<dyn Fn(&str) -> bool>) -> PredicateValidator {
+        Self::new(accept_input, Box::new(|_| true))
+    }
+
+    pub fn valid_only(valid: Box<dyn Fn(&str) -> bool>) -> PredicateValidator {
+        Self::new(Box::new(|_| true), valid)
+    }
+}
+
+impl TextValidator for PredicateValidator {
+
+    fn accepts_input(&self, _old_text: &str, new_text: &str) -> bool {
+        (self.accept_input)(new_text)
+    }
+
+    fn is_valid(&self, text: &str) -> bool {
+        (self.valid)(text)
+    }
+}
+
+pub struct CombinedValidator {
+
+    validators: Vec<Box<dyn TextValidator>>
+}
+
+impl CombinedValidator {
+
+    pub fn new(validators: Vec<Box<dyn TextValidator>>) -> CombinedValidator {
+        CombinedValidator {
+            validators
+        }
+    }
+}
+
+impl TextValidator for CombinedValidator {
+
+    fn accepts_input(&self, old_text: &str, new_text: &str) -> bool {
+        self.validators.iter().all(|validator| validator.accepts_input(old_text, new_text))
+    }
+
+    fn is_valid(&self, text: &str) -> bool {
+        self.validators.iter().all(|validator| validator.is_valid(text))
+    }
+}
+
+pub fn hex_color_validator() -> PredicateValidator {
+    PredicateValidator::new(
+        Box::new(|text| text.len() <= 7 && text.char_indices().all(|(index, character)| match index {
+            0 => character == '#' || character.is_ascii_hexdigit(),
+            _ => character.is_ascii_hexdigit()
+        })),
+        Box::new(|text| {
+            let digits = text.trim_start_matches('#');
+            (digits.len() == 6 || digits.len() == 3) && digits.len() + 1 >= text.len()
+        })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn numeric_validator() {
+        let validator = NumericValidator::new(false);
+        assert!(validator.accepts_input("", "123"));
+        assert!(!validator.accepts_input("12", "12a"));
+        assert!(!validator.accepts_input("", "-1"));
+        assert!(validator.is_valid("42"));
+        assert!(validator.is_valid(""));
+        assert!(!validator.is_valid("4.2"));
+    }
+
+    #[test]
+    fn negative_numeric_validator() {
+        let validator = NumericValidator::new(true);
+        assert!(validator.accepts_input("", "-"));
+        assert!(validator.accepts_input("-", "-12"));
+        assert!(!validator.accepts_input("1", "1-"));
+        assert!(!validator.accepts_input("-", "--"));
+        assert!(validator.is_valid("-12"));
+        assert!(!validator.is_valid("-"));
+    }
+
+    #[test]
+    fn max_length_validator() {
+        let validator = MaxLengthValidator::new(3);
+        assert!(validator.accepts_input("ab", "abc"));
+        assert!(!validator.accepts_input("abc", "abcd"));
+
+        // Combined graphemes count as a single character
+        assert!(validator.accepts_input("", "e\u{301}e\u{301}e\u{301}"));
+
+        // Text that was already too long may still be shortened
+        assert!(validator.accepts_input("abcdef", "abcde"));
+        assert!(validator.is_valid("abc"));
+        assert!(!validator.is_valid("abcd"));
+    }
+
+    #[test]
+    fn predicate_validator() {
+        let validator = PredicateValidator::new(Box::new(|text| !text.contains(' ')), Box::new(|text| text.len() >= 2));
+        assert!(validator.accepts_input("", "a"));
+        assert!(!validator.accepts_input("a", "a b"));
+        assert!(!validator.is_valid("a"));
+        assert!(validator.is_valid("ab"));
+
+        let input_only = PredicateValidator::input_only(Box::new(|text| text.is_empty()));
+        assert!(!input_only.accepts_input("", "a"));
+        assert!(input_only.is_valid("a"));
+
+        let valid_only = PredicateValidator::valid_only(Box::new(|text| text.is_empty()));
+        assert!(valid_only.accepts_input("", "a"));
+        assert!(!valid_only.is_valid("a"));
+    }
+
+    #[test]
+    fn combined_validator() {
+        let validator = CombinedValidator::new(vec![Box::new(NumericValidator::new(false)), Box::new(MaxLengthValidator::new(2))]);
+        assert!(validator.accepts_input("1", "12"));
+        assert!(!validator.accepts_input("12", "123"));
+        assert!(!validator.accepts_input("1", "1a"));
+        assert!(validator.is_valid("12"));
+        assert!(!validator.is_valid("123"));
+    }
+
+    #[test]
+    fn hex_color() {
+        let validator = hex_color_validator();
+        assert!(validator.accepts_input("", "#"));
+        assert!(validator.accepts_input("#", "#a1"));
+        assert!(validator.accepts_input("", "a1"));
+        assert!(!validator.accepts_input("#", "##"));
+        assert!(!validator.accepts_input("#", "#g"));
+        assert!(!validator.accepts_input("#123456", "#1234567"));
+        assert!(validator.is_valid("#123456"));
+        assert!(validator.is_valid("#abc"));
+        assert!(validator.is_valid("abc"));
+        assert!(!validator.is_valid("#1234"));
+        assert!(!validator.is_valid("#"));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::ComponentError;
+use crate::FallibleComponent;
+use crate::behavior::*;
+use crate::button::{ToggleAction, ToggleButton};
+
+use std::cell::RefCell;
+use std::rc::*;
+
+use wasmuri_container::*;
+use wasmuri_core::*;
+use wasmuri_text::Font;
+
+pub const UNCHECKED_GLYPH: &str = "\u{2610}";
+pub const CHECKED_GLYPH: &str = "\u{2611}";
+
+fn checkbox_text(label: &str, checked: bool) -> String {
+    match checked {
+        true => format!("{} {}", CHECKED_GLYPH, label),
+        false => format!("{} {}", UNCHECKED_GLYPH, label)
+    }
+}
+
+pub struct Checkbox {
+
+    toggle: ToggleButton
+}
+
+impl Checkbox {
+
+    pub fn new(label: &str, font: &Rc<Font>, region: Region, colors: TextColors, initially_checked: bool, on_toggle: ToggleAction) -> Checkbox {
+        let render_helper = ButtonTextRenderController::simple_tuple(&checkbox_text(label, initially_checked), font, 
+                left_button_location(region), colors);
+        let label = label.to_string();
+        Checkbox {
+            toggle: ToggleButton::with_state_hook(render_helper.0, render_helper.1, initially_checked, on_toggle, 
+                    Box::new(move |controller, checked| controller.set_text(&checkbox_text(&label, checked))))
+        }
+    }
+
+    pub fn celled(label: &str, font: &Rc<Font>, region: Region, colors: TextColors, initially_checked: bool, on_toggle: ToggleAction) -> Rc<RefCell<Checkbox>> {
+        Rc::new(RefCell::new(Self::new(label, font, region, colors, initially_checked, on_toggle)))
+    }
+
+    pub fn get_controller(&self) -> Rc<RefCell<dyn TextRenderController>> {
+        self.toggle.get_controller()
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.toggle.is_checked()
+    }
+
+    pub fn set_checked(&self, checked: bool) {
+        self.toggle.set_checked(checked);
+    }
+
+    pub fn set_region(&self, new_region: Region) -> Result<(), ComponentError> {
+        self.toggle.set_region(new_region)
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.toggle.is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) -> Result<(), ComponentError> {
+        self.toggle.set_visible(visible)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.toggle.is_enabled()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.toggle.set_enabled(enabled);
+    }
+}
+
+impl Focusable for Checkbox {
+
+    fn is_focused(&self) -> bool {
+        self.toggle.is_focused()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.toggle.set_focused(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        self.toggle.can_focus()
+    }
+
+    fn set_focus_handle(&mut self, handle: Option<FocusHandle>) {
+        self.toggle.set_focus_handle(handle);
+    }
+}
+
+impl Component for Checkbox {
+
+    fn create_behaviors(&mut self) -> Vec<Rc<RefCell<dyn ComponentBehavior>>> {
+        self.toggle.create_behaviors()
+    }
+}
+
+impl FallibleComponent for Checkbox {
+
+    fn take_attach_error(&self) -> Option<ComponentError> {
+        self.toggle.take_attach_error()
+    }
+}
\ No newline at end of file
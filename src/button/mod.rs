@@ -1,3 +1,9 @@
 mod text;
+mod toggle;
+mod checkbox;
+mod radio;
 
-pub use text::*;
\ No newline at end of file
+pub use text::*;
+pub use toggle::*;
+pub use checkbox::*;
+pub use radio::*;
\ No newline at end of file
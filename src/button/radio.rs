@@ -0,0 +1,380 @@
+use crate::{ComponentError, upgrade_agent};
+use crate::FallibleComponent;
+use crate::behavior::*;
+
+use std::cell::{Cell, RefCell};
+use std::rc::*;
+
+use wasmuri_container::*;
+use wasmuri_core::*;
+use wasmuri_text::Font;
+
+pub const UNSELECTED_GLYPH: &str = "\u{25CB}";
+pub const SELECTED_GLYPH: &str = "\u{25C9}";
+
+pub type RadioSelectAction = Box<dyn FnMut(&mut ComponentAgent, usize)>;
+
+fn option_text(label: &str, selected: bool) -> String {
+    match selected {
+        true => format!("{} {}", SELECTED_GLYPH, label),
+        false => format!("{} {}", UNSELECTED_GLYPH, label)
+    }
+}
+
+struct RadioState {
+
+    labels: Vec<String>,
+    controllers: Vec<Rc<RefCell<dyn TextRenderController>>>,
+
+    selected: Cell<Option<usize>>,
+    focused: Cell<bool>,
+    focus_handle: RefCell<Option<FocusHandle>>,
+
+    on_select: RefCell<RadioSelectAction>
+}
+
+impl RadioState {
+
+    fn get_focus_index(&self) -> usize {
+        self.selected.get().unwrap_or(0)
+    }
+
+    fn update_option(&self, controller: &mut dyn TextRenderController, index: usize, selected: bool, focused: bool) {
+        controller.set_text(&option_text(&self.labels[index], selected));
+        controller.set_checked(selected);
+        controller.set_focused(focused);
+    }
+
+    // The controller of the clicked option is already borrowed by the ClickActionBehavior, so it has to be passed in
+    fn set_selected(&self, new_selected: Option<usize>, clicked: Option<(usize, &mut dyn TextRenderController)>) {
+        self.selected.set(new_selected);
+        let focus_index = self.get_focus_index();
+        let focused = self.focused.get();
+        let clicked_index = clicked.as_ref().map(|(index, _)| *index);
+        for (index, controller) in self.controllers.iter().enumerate() {
+            if Some(index) != clicked_index {
+                self.update_option(&mut *controller.borrow_mut(), index, Some(index) == new_selected, focused && index == focus_index);
+            }
+        }
+        if let Some((index, controller)) = clicked {
+            self.update_option(controller, index, Some(index) == new_selected, focused && index == focus_index);
+        }
+    }
+
+    fn select(&self, agent: &mut ComponentAgent, index: usize, clicked: Option<&mut dyn TextRenderController>) -> bool {
+        if self.selected.get() == Some(index) {
+            return false;
+        }
+        self.set_selected(Some(index), clicked.map(|controller| (index, controller)));
+        self.on_select.borrow_mut().as_mut()(agent, index);
+        true
+    }
+
+    fn step_selection(&self, agent: &mut ComponentAgent, forward: bool) -> bool {
+        let amount = self.controllers.len();
+        if amount == 0 {
+            return false;
+        }
+        let mut index = match self.selected.get() {
+            Some(selected) => selected,
+            None => match forward {
+                true => amount - 1,
+                false => 0
+            }
+        };
+
+        // Skip disabled options, but give up after a full round
+        for _ in 0..amount {
+            index = match forward {
+                true => (index + 1) % amount,
+                false => (index + amount - 1) % amount
+            };
+            if self.controllers[index].borrow().is_enabled() {
+                return self.select(agent, index, None);
+            }
+        }
+        false
+    }
+}
+
+struct RadioKeyBehavior {
+
+    agent: Option<Weak<RefCell<ComponentAgent>>>,
+    state: Rc<RadioState>,
+
+    attached: bool,
+    visible: bool,
+    key_listening: bool
+}
+
+impl RadioKeyBehavior {
+
+    fn set_visible(&mut self, visible: bool){
+        self.visible = visible;
+        self.update_key_listener_if_attached();
+    }
+
+    fn update_key_listener_if_attached(&mut self){
+
+        // A removed group doesn't have a key listener to update
+        if let Some(agent_cell) = upgrade_agent(&self.agent, "RadioGroup").ok().filter(|_| self.attached) {
+            self.update_key_listener(&mut agent_cell.borrow_mut());
+        }
+    }
+
+    // The keys only control the group while it has the focus, so it doesn't need to listen otherwise
+    fn update_key_listener(&mut self, agent: &mut ComponentAgent){
+        let should_listen = self.visible && self.state.focused.get();
+        if self.key_listening != should_listen {
+            match should_listen {
+                true => agent.make_key_down_listener(10),
+                false => agent.remove_key_down_listener()
+            };
+            self.key_listening = should_listen;
+        }
+    }
+}
+
+impl ComponentBehavior for RadioKeyBehavior {
+
+    fn attach(&mut self, agent: &mut dyn LayerAgent){
+        self.attached = true;
+        if self.visible && self.state.focused.get() {
+            agent.make_key_down_listener(10);
+            self.key_listening = true;
+        }
+    }
+
+    fn set_agent(&mut self, agent: Weak<RefCell<ComponentAgent>>){
+        self.agent = Some(agent);
+    }
+
+    fn get_agent(&self) -> &Weak<RefCell<ComponentAgent>> {
+        self.agent.as_ref().expect("Agent should have been set by now")
+    }
+
+    fn key_down(&mut self, params: &mut KeyDownParams) -> bool {
+        if !self.state.focused.get() {
+            return false;
+        }
+        let forward = match params.keys.get_key() {
+            "ArrowDown" | "ArrowRight" | "Down" | "Right" => true,
+            "ArrowUp" | "ArrowLeft" | "Up" | "Left" => false,
+            " " | "Spacebar" => {
+                let focus_index = self.state.get_focus_index();
+                if focus_index < self.state.controllers.len() && self.state.controllers[focus_index].borrow().is_enabled() {
+                    let agent_cell = self.get_agent().upgrade().expect("Agent shouldn't have been dropped");
+                    self.state.select(&mut agent_cell.borrow_mut(), focus_index, None);
+                }
+                return true;
+            },
+            _ => return false
+        };
+        let agent_cell = self.get_agent().upgrade().expect("Agent shouldn't have been dropped");
+        self.state.step_selection(&mut agent_cell.borrow_mut(), forward);
+        true
+    }
+}
+
+pub struct RadioGroup {
+
+    render_behaviors: Vec<Rc<RefCell<dyn ComponentBehavior>>>,
+    click_behaviors: Vec<Rc<RefCell<ClickActionBehavior>>>,
+    key_behavior: Rc<RefCell<RadioKeyBehavior>>,
+
+    state: Rc<RadioState>
+}
+
+impl RadioGroup {
+
+    pub fn new(labels: &[&str], font: &Rc<Font>, regions: Vec<Region>, colors: TextColors,
+            initially_selected: Option<usize>, on_select: RadioSelectAction) -> RadioGroup {
+        assert_eq!(labels.len(), regions.len(), "Every radio option needs its own region");
+
+        let mut render_behaviors = Vec::with_capacity(labels.len());
+        let mut controllers = Vec::with_capacity(labels.len());
+        for (index, (label, region)) in labels.iter().zip(regions).enumerate() {
+            let render_helper = ButtonTextRenderController::simple_tuple(&option_text(label, initially_selected == Some(index)), font,
+                    Box::new(AlignedTextLocation::new(region, TextAlignment::LeftCenter, false)), colors);
+            render_helper.1.borrow_mut().set_checked(initially_selected == Some(index));
+            render_behaviors.push(render_helper.0);
+            controllers.push(render_helper.1);
+        }
+
+        let state = Rc::new(RadioState {
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            controllers,
+
+            selected: Cell::new(initially_selected),
+            focused: Cell::new(false),
+            focus_handle: RefCell::new(None),
+
+            on_select: RefCell::new(on_select)
+        });
+
+        let key_behavior = Rc::new(RefCell::new(RadioKeyBehavior {
+            agent: None,
+            state: Rc::clone(&state),
+
+            attached: false,
+            visible: true,
+            key_listening: false
+        }));
+
+        let click_behaviors = state.controllers.iter().enumerate().map(|(index, controller)| {
+            let click_state = Rc::clone(&state);
+            let click_key_behavior = Rc::clone(&key_behavior);
+            let on_click: ClickAction = Box::new(move |agent, controller, _source| {
+
+                // Clicking an option focuses the group, like clicking an edit field does
+                if !click_state.focused.get() {
+                    if let Some(focus_handle) = click_state.focus_handle.borrow().as_ref() {
+                        focus_handle.claim_focus();
+                    }
+                    click_state.focused.set(true);
+
+                    // The agent of this closure belongs to the clicked option, so the key behavior uses its own
+                    click_key_behavior.borrow_mut().update_key_listener_if_attached();
+                }
+                if !click_state.select(agent, index, Some(&mut *controller)) {
+                    click_state.set_selected(click_state.selected.get(), Some((index, controller)));
+                }
+            });
+            ClickActionBehavior::celled(on_click, Rc::clone(controller))
+        }).collect();
+
+        RadioGroup {
+            render_behaviors,
+            click_behaviors,
+            key_behavior,
+
+            state
+        }
+    }
+
+    pub fn celled(labels: &[&str], font: &Rc<Font>, regions: Vec<Region>, colors: TextColors,
+            initially_selected: Option<usize>, on_select: RadioSelectAction) -> Rc<RefCell<RadioGroup>> {
+        Rc::new(RefCell::new(Self::new(labels, font, regions, colors, initially_selected, on_select)))
+    }
+
+    pub fn get_option_count(&self) -> usize {
+        self.state.controllers.len()
+    }
+
+    pub fn get_option_controller(&self, index: usize) -> Option<Rc<RefCell<dyn TextRenderController>>> {
+        self.state.controllers.get(index).map(Rc::clone)
+    }
+
+    pub fn get_selected(&self) -> Option<usize> {
+        self.state.selected.get()
+    }
+
+    pub fn get_selected_label(&self) -> Option<&str> {
+        self.state.selected.get().map(|index| self.state.labels[index].as_str())
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), ComponentError> {
+        match index < self.get_option_count() {
+            true => Ok(()),
+            false => Err(ComponentError::IndexOutOfRange("RadioGroup", index))
+        }
+    }
+
+    pub fn set_selected(&self, selected: Option<usize>) -> Result<(), ComponentError> {
+        if let Some(index) = selected {
+            self.check_index(index)?;
+        }
+        self.state.set_selected(selected, None);
+        Ok(())
+    }
+
+    pub fn set_option_region(&self, index: usize, new_region: Region) -> Result<(), ComponentError> {
+        self.check_index(index)?;
+        let controller = &self.state.controllers[index];
+        let old_region = controller.borrow().get_max_region();
+        controller.borrow_mut().set_region(new_region)?;
+        if let Err(error) = self.click_behaviors[index].borrow_mut().update_click_space() {
+            controller.borrow_mut().set_region(old_region).expect("Should be able to move back to the old region");
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.key_behavior.borrow().visible
+    }
+
+    fn set_option_visible(&self, index: usize, visible: bool) -> Result<(), ComponentError> {
+        let controller = &self.state.controllers[index];
+        controller.borrow_mut().set_visible(visible)?;
+        if let Err(error) = self.click_behaviors[index].borrow_mut().set_visible(visible) {
+            controller.borrow_mut().set_visible(!visible).expect("Should be able to restore the old visibility");
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn set_visible(&self, visible: bool) -> Result<(), ComponentError> {
+
+        // Showing can fail halfway, so the options that were already changed get their old visibility back
+        for index in 0..self.get_option_count() {
+            if let Err(error) = self.set_option_visible(index, visible) {
+                for changed_index in 0..index {
+                    self.set_option_visible(changed_index, !visible).expect("Should be able to restore the old visibility");
+                }
+                return Err(error);
+            }
+        }
+        if !visible && self.state.focused.get() {
+            self.state.focused.set(false);
+            self.state.set_selected(self.state.selected.get(), None);
+        }
+        self.key_behavior.borrow_mut().set_visible(visible);
+        Ok(())
+    }
+
+    pub fn set_option_enabled(&self, index: usize, enabled: bool) -> Result<(), ComponentError> {
+        self.check_index(index)?;
+        self.state.controllers[index].borrow_mut().set_enabled(enabled);
+        Ok(())
+    }
+}
+
+impl Focusable for RadioGroup {
+
+    fn is_focused(&self) -> bool {
+        self.state.focused.get()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.state.focused.set(focused);
+        self.state.set_selected(self.state.selected.get(), None);
+        self.key_behavior.borrow_mut().update_key_listener_if_attached();
+    }
+
+    fn can_focus(&self) -> bool {
+        self.is_visible() && self.state.controllers.iter().any(|controller| controller.borrow().is_enabled())
+    }
+
+    fn set_focus_handle(&mut self, handle: Option<FocusHandle>) {
+        self.state.focus_handle.replace(handle);
+    }
+}
+
+impl Component for RadioGroup {
+
+    fn create_behaviors(&mut self) -> Vec<Rc<RefCell<dyn ComponentBehavior>>> {
+        let mut behaviors: Vec<Rc<RefCell<dyn ComponentBehavior>>> = self.render_behaviors.iter().map(Rc::clone).collect();
+        behaviors.extend(self.click_behaviors.iter().map(|behavior| Rc::clone(behavior) as Rc<RefCell<dyn ComponentBehavior>>));
+        behaviors.push(Rc::clone(&self.key_behavior) as Rc<RefCell<dyn ComponentBehavior>>);
+        behaviors
+    }
+}
+
+impl FallibleComponent for RadioGroup {
+
+    fn take_attach_error(&self) -> Option<ComponentError> {
+        let render_error = self.state.controllers.iter().find_map(|controller| controller.borrow_mut().take_attach_error());
+        render_error.or_else(|| self.click_behaviors.iter().find_map(|behavior| behavior.borrow_mut().take_attach_error()))
+    }
+}
\ No newline at end of file
@@ -1,21 +1,24 @@
+use crate::ComponentError;
+use crate::FallibleComponent;
 use crate::behavior::*;
 
 use std::cell::RefCell;
 use std::rc::*;
 
 use wasmuri_container::*;
+use wasmuri_core::Region;
 
 pub struct TextButton {
 
     render_behavior: Rc<RefCell<dyn ComponentBehavior>>,
     render_controller: Rc<RefCell<dyn TextRenderController>>,
-    click_behavior: Rc<RefCell<dyn ComponentBehavior>>
+    click_behavior: Rc<RefCell<ClickActionBehavior>>
 }
 
 impl TextButton {
 
     pub fn new(render_behavior: Rc<RefCell<dyn ComponentBehavior>>, render_controller: Rc<RefCell<dyn TextRenderController>>,
-    on_click: Box<dyn FnMut(&mut ComponentAgent, &mut dyn TextRenderController, &mut MouseClickParams)>) -> TextButton {
+    on_click: ClickAction) -> TextButton {
         TextButton {
             click_behavior: ClickActionBehavior::celled(on_click, Rc::clone(&render_controller)),
             render_behavior,
@@ -24,18 +27,94 @@ impl TextButton {
     }
 
     pub fn celled(render_helper: (Rc<RefCell<dyn ComponentBehavior>>, Rc<RefCell<dyn TextRenderController>>),
-    on_click: Box<dyn FnMut(&mut ComponentAgent, &mut dyn TextRenderController, &mut MouseClickParams)>) -> Rc<RefCell<TextButton>> {
+    on_click: ClickAction) -> Rc<RefCell<TextButton>> {
         Rc::new(RefCell::new(TextButton::new(render_helper.0, render_helper.1, on_click)))
     }
 
     pub fn get_controller(&self) -> Rc<RefCell<dyn TextRenderController>> {
         Rc::clone(&self.render_controller)
     }
+
+    pub fn set_region(&self, new_region: Region) -> Result<(), ComponentError> {
+        let old_region = self.render_controller.borrow().get_max_region();
+        self.render_controller.borrow_mut().set_region(new_region)?;
+        if let Err(error) = self.click_behavior.borrow_mut().update_click_space() {
+            self.render_controller.borrow_mut().set_region(old_region).expect("Should be able to move back to the old region");
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn set_on_middle_click(&self, on_middle_click: Option<ClickAction>) {
+        self.click_behavior.borrow_mut().set_on_middle_click(on_middle_click);
+    }
+
+    pub fn set_on_secondary_click(&self, on_secondary_click: Option<ClickAction>) {
+        self.click_behavior.borrow_mut().set_on_secondary_click(on_secondary_click);
+    }
+
+    pub fn set_on_double_click(&self, on_double_click: Option<ClickAction>) {
+        self.click_behavior.borrow_mut().set_on_double_click(on_double_click);
+    }
+
+    pub fn set_double_click_threshold(&self, max_time: f64, max_distance: f32) {
+        self.click_behavior.borrow_mut().set_double_click_threshold(max_time, max_distance);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.render_controller.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) -> Result<(), ComponentError> {
+        self.render_controller.borrow_mut().set_visible(visible)?;
+        if let Err(error) = self.click_behavior.borrow_mut().set_visible(visible) {
+            self.render_controller.borrow_mut().set_visible(!visible).expect("Should be able to restore the old visibility");
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.render_controller.borrow().is_enabled()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.render_controller.borrow_mut().set_enabled(enabled);
+    }
+}
+
+impl Focusable for TextButton {
+
+    fn is_focused(&self) -> bool {
+        self.render_controller.borrow().is_focused()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.render_controller.borrow_mut().set_focused(focused);
+        self.click_behavior.borrow_mut().set_focused(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        let render_controller = self.render_controller.borrow();
+        render_controller.is_enabled() && render_controller.is_visible()
+    }
+
+    fn set_focus_handle(&mut self, handle: Option<FocusHandle>) {
+        self.click_behavior.borrow_mut().set_focus_handle(handle);
+    }
 }
 
 impl Component for TextButton {
 
     fn create_behaviors(&mut self) -> Vec<Rc<RefCell<dyn ComponentBehavior>>> {
-        vec![Rc::clone(&self.render_behavior), Rc::clone(&self.click_behavior)]
+        vec![Rc::clone(&self.render_behavior), Rc::clone(&self.click_behavior) as Rc<RefCell<dyn ComponentBehavior>>]
+    }
+}
+
+impl FallibleComponent for TextButton {
+
+    fn take_attach_error(&self) -> Option<ComponentError> {
+        let render_error = self.render_controller.borrow_mut().take_attach_error();
+        render_error.or_else(|| self.click_behavior.borrow_mut().take_attach_error())
     }
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+use crate::ComponentError;
+use crate::FallibleComponent;
+use crate::behavior::*;
+
+use std::cell::{Cell, RefCell};
+use std::rc::*;
+
+use wasmuri_container::*;
+use wasmuri_core::Region;
+
+pub type ToggleAction = Box<dyn FnMut(&mut ComponentAgent, bool)>;
+
+pub type ToggleStateHook = Box<dyn Fn(&mut dyn TextRenderController, bool)>;
+
+pub struct ToggleButton {
+
+    render_behavior: Rc<RefCell<dyn ComponentBehavior>>,
+    render_controller: Rc<RefCell<dyn TextRenderController>>,
+    click_behavior: Rc<RefCell<ClickActionBehavior>>,
+
+    checked: Rc<Cell<bool>>,
+    state_hook: Rc<ToggleStateHook>
+}
+
+impl ToggleButton {
+
+    pub fn new(render_behavior: Rc<RefCell<dyn ComponentBehavior>>, render_controller: Rc<RefCell<dyn TextRenderController>>,
+            initially_checked: bool, on_toggle: ToggleAction) -> ToggleButton {
+        Self::with_state_hook(render_behavior, render_controller, initially_checked, on_toggle, Box::new(|_, _| {}))
+    }
+
+    pub fn with_state_hook(render_behavior: Rc<RefCell<dyn ComponentBehavior>>, render_controller: Rc<RefCell<dyn TextRenderController>>,
+            initially_checked: bool, mut on_toggle: ToggleAction, state_hook: ToggleStateHook) -> ToggleButton {
+        render_controller.borrow_mut().set_checked(initially_checked);
+
+        let checked = Rc::new(Cell::new(initially_checked));
+        let state_hook = Rc::new(state_hook);
+        let click_checked = Rc::clone(&checked);
+        let click_state_hook = Rc::clone(&state_hook);
+        let on_click: ClickAction = Box::new(move |agent, controller, _source| {
+            let new_checked = !click_checked.get();
+            click_checked.set(new_checked);
+            controller.set_checked(new_checked);
+            click_state_hook(controller, new_checked);
+            on_toggle(agent, new_checked);
+        });
+
+        ToggleButton {
+            click_behavior: ClickActionBehavior::celled(on_click, Rc::clone(&render_controller)),
+            render_behavior,
+            render_controller,
+
+            checked,
+            state_hook
+        }
+    }
+
+    pub fn celled(render_helper: (Rc<RefCell<dyn ComponentBehavior>>, Rc<RefCell<dyn TextRenderController>>),
+            initially_checked: bool, on_toggle: ToggleAction) -> Rc<RefCell<ToggleButton>> {
+        Rc::new(RefCell::new(Self::new(render_helper.0, render_helper.1, initially_checked, on_toggle)))
+    }
+
+    pub fn get_controller(&self) -> Rc<RefCell<dyn TextRenderController>> {
+        Rc::clone(&self.render_controller)
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked.get()
+    }
+
+    pub fn set_checked(&self, checked: bool) {
+        self.checked.set(checked);
+        let mut controller = self.render_controller.borrow_mut();
+        controller.set_checked(checked);
+        (self.state_hook)(&mut *controller, checked);
+    }
+
+    pub fn set_region(&self, new_region: Region) -> Result<(), ComponentError> {
+        let old_region = self.render_controller.borrow().get_max_region();
+        self.render_controller.borrow_mut().set_region(new_region)?;
+        if let Err(error) = self.click_behavior.borrow_mut().update_click_space() {
+            self.render_controller.borrow_mut().set_region(old_region).expect("Should be able to move back to the old region");
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.render_controller.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) -> Result<(), ComponentError> {
+        self.render_controller.borrow_mut().set_visible(visible)?;
+        if let Err(error) = self.click_behavior.borrow_mut().set_visible(visible) {
+            self.render_controller.borrow_mut().set_visible(!visible).expect("Should be able to restore the old visibility");
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.render_controller.borrow().is_enabled()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.render_controller.borrow_mut().set_enabled(enabled);
+    }
+}
+
+impl Focusable for ToggleButton {
+
+    fn is_focused(&self) -> bool {
+        self.render_controller.borrow().is_focused()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.render_controller.borrow_mut().set_focused(focused);
+        self.click_behavior.borrow_mut().set_focused(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        let render_controller = self.render_controller.borrow();
+        render_controller.is_enabled() && render_controller.is_visible()
+    }
+
+    fn set_focus_handle(&mut self, handle: Option<FocusHandle>) {
+        self.click_behavior.borrow_mut().set_focus_handle(handle);
+    }
+}
+
+impl Component for ToggleButton {
+
+    fn create_behaviors(&mut self) -> Vec<Rc<RefCell<dyn ComponentBehavior>>> {
+        vec![Rc::clone(&self.render_behavior), Rc::clone(&self.click_behavior) as Rc<RefCell<dyn ComponentBehavior>>]
+    }
+}
+
+impl FallibleComponent for ToggleButton {
+
+    fn take_attach_error(&self) -> Option<ComponentError> {
+        let render_error = self.render_controller.borrow_mut().take_attach_error();
+        render_error.or_else(|| self.click_behavior.borrow_mut().take_attach_error())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::rc::*;
+
+use wasmuri_container::{Component, ComponentAgent};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComponentError {
+
+    RenderSpaceTaken(&'static str),
+    ClickSpaceTaken(&'static str),
+    IndexOutOfRange(&'static str, usize),
+    RegionFixed(&'static str),
+    AgentMissing(&'static str)
+}
+
+impl fmt::Display for ComponentError {
+
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ComponentError::RenderSpaceTaken(owner) => write!(f, "{} couldn't claim its render space, it probably overlaps another component", owner),
+            ComponentError::ClickSpaceTaken(owner) => write!(f, "{} couldn't claim its click space, it probably overlaps another component", owner),
+            ComponentError::IndexOutOfRange(owner, index) => write!(f, "{} doesn't have an option with index {}", owner, index),
+            ComponentError::RegionFixed(owner) => write!(f, "{} can't be moved to another region", owner),
+            ComponentError::AgentMissing(owner) => write!(f, "{} lost its component agent, its component was probably removed", owner)
+        }
+    }
+}
+
+impl Error for ComponentError {}
+
+// The behaviors only keep a weak reference to their agent, which is gone once the layer dropped their component
+pub(crate) fn upgrade_agent(agent: &Option<Weak<RefCell<ComponentAgent>>>, owner: &'static str) -> Result<Rc<RefCell<ComponentAgent>>, ComponentError> {
+    agent.as_ref().and_then(|agent| agent.upgrade()).ok_or(ComponentError::AgentMissing(owner))
+}
+
+// The layer calls attach without a way to report failure, so the behaviors remember the error until it is taken
+pub trait FallibleComponent: Component {
+
+    fn take_attach_error(&self) -> Option<ComponentError>;
+}
\ No newline at end of file
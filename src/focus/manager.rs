@@ -0,0 +1,68 @@
+use crate::behavior::*;
+
+use std::cell::RefCell;
+use std::rc::*;
+
+use wasmuri_container::*;
+
+pub struct FocusManager {
+
+    behavior: Rc<RefCell<FocusBehavior>>
+}
+
+impl Default for FocusManager {
+
+    fn default() -> FocusManager {
+        Self::new()
+    }
+}
+
+impl FocusManager {
+
+    pub fn new() -> FocusManager {
+        FocusManager {
+            behavior: FocusBehavior::celled()
+        }
+    }
+
+    pub fn celled() -> Rc<RefCell<FocusManager>> {
+        Rc::new(RefCell::new(Self::new()))
+    }
+
+    pub fn add_focusable(&self, focusable: Rc<RefCell<dyn Focusable>>) {
+        focusable.borrow_mut().set_focus_handle(Some(FocusHandle::new(&self.behavior, &focusable)));
+        self.behavior.borrow_mut().add_focusable(focusable);
+    }
+
+    pub fn remove_focusable(&self, focusable: &Rc<RefCell<dyn Focusable>>) {
+        focusable.borrow_mut().set_focus_handle(None);
+        self.behavior.borrow_mut().remove_focusable(focusable);
+    }
+
+    pub fn get_focused(&self) -> Option<Rc<RefCell<dyn Focusable>>> {
+        self.behavior.borrow().get_focused()
+    }
+
+    pub fn focus(&self, component: &Rc<RefCell<dyn Focusable>>) {
+        self.behavior.borrow_mut().focus(component);
+    }
+
+    pub fn blur(&self) {
+        self.behavior.borrow_mut().blur();
+    }
+
+    pub fn focus_next(&self) {
+        self.behavior.borrow_mut().focus_next();
+    }
+
+    pub fn focus_previous(&self) {
+        self.behavior.borrow_mut().focus_previous();
+    }
+}
+
+impl Component for FocusManager {
+
+    fn create_behaviors(&mut self) -> Vec<Rc<RefCell<dyn ComponentBehavior>>> {
+        vec![Rc::clone(&self.behavior) as Rc<RefCell<dyn ComponentBehavior>>]
+    }
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+use crate::ComponentError;
+use crate::FallibleComponent;
+use crate::behavior::*;
+
+use std::cell::RefCell;
+use std::rc::*;
+
+use wasmuri_container::*;
+use wasmuri_core::Region;
+
+pub struct TextArea {
+
+    render_behavior: Rc<RefCell<dyn ComponentBehavior>>,
+    render_controller: Rc<RefCell<TextAreaRenderController>>
+}
+
+impl TextArea {
+
+    pub fn new(render_helper: (Rc<RefCell<dyn ComponentBehavior>>, Rc<RefCell<TextAreaRenderController>>)) -> TextArea {
+        TextArea {
+            render_behavior: render_helper.0,
+            render_controller: render_helper.1
+        }
+    }
+
+    pub fn celled(render_helper: (Rc<RefCell<dyn ComponentBehavior>>, Rc<RefCell<TextAreaRenderController>>)) -> Rc<RefCell<TextArea>> {
+        Rc::new(RefCell::new(Self::new(render_helper)))
+    }
+
+    pub fn get_current_text(&self) -> String {
+        self.render_controller.borrow().get_current_text()
+    }
+
+    pub fn get_controller(&self) -> Rc<RefCell<TextAreaRenderController>> {
+        Rc::clone(&self.render_controller)
+    }
+
+    pub fn set_submit_on_control_enter(&self, submit_on_control_enter: bool) {
+        self.render_controller.borrow_mut().set_submit_on_control_enter(submit_on_control_enter);
+    }
+
+    pub fn set_region(&self, new_region: Region) -> Result<(), ComponentError> {
+        self.render_controller.borrow_mut().set_region(new_region)
+    }
+
+    pub fn set_on_change(&self, on_change: Option<EditChangeAction>) {
+        self.render_controller.borrow_mut().set_on_change(on_change);
+    }
+
+    pub fn set_on_submit(&self, on_submit: Option<EditSubmitAction>) {
+        self.render_controller.borrow_mut().set_on_submit(on_submit);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.render_controller.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) -> Result<(), ComponentError> {
+        self.render_controller.borrow_mut().set_visible(visible)
+    }
+}
+
+impl Focusable for TextArea {
+
+    fn is_focused(&self) -> bool {
+        self.render_controller.borrow().is_focused()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.render_controller.borrow_mut().set_focused(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        self.render_controller.borrow().is_visible()
+    }
+
+    fn set_focus_handle(&mut self, handle: Option<FocusHandle>) {
+        self.render_controller.borrow_mut().set_focus_handle(handle);
+    }
+}
+
+impl Component for TextArea {
+
+    fn create_behaviors(&mut self) -> Vec<Rc<RefCell<dyn ComponentBehavior>>> {
+        vec![Rc::clone(&self.render_behavior)]
+    }
+}
+
+impl FallibleComponent for TextArea {
+
+    fn take_attach_error(&self) -> Option<ComponentError> {
+        self.render_controller.borrow_mut().take_attach_error()
+    }
+}
\ No newline at end of file
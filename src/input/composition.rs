@@ -0,0 +1,82 @@
+use crate::behavior::*;
+
+use std::cell::RefCell;
+use std::rc::*;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+
+use web_sys::{CompositionEvent, HtmlInputElement};
+
+pub struct CompositionInput {
+
+    element: HtmlInputElement,
+    _listeners: Vec<Closure<dyn FnMut(CompositionEvent)>>
+}
+
+impl CompositionInput {
+
+    pub fn attach_to(controller: &Rc<RefCell<EditTextRenderController>>) -> Rc<CompositionInput> {
+        let document = web_sys::window().expect("Should have a window").document().expect("Window should have a document");
+        let element = document.create_element("input").expect("Should be able to create an input element")
+                .dyn_into::<HtmlInputElement>().expect("Input element should be an HtmlInputElement");
+
+        // The element must be focusable to receive composition events, but the user shouldn't see it
+        let style = element.style();
+        for (property, value) in &[("position", "fixed"), ("left", "0"), ("top", "0"), ("width", "1px"), ("height", "1px"), 
+                ("opacity", "0"), ("pointer-events", "none")] {
+            style.set_property(property, value).expect("Should be able to style the composition input");
+        }
+        document.body().expect("Document should have a body").append_child(&element).expect("Should be able to add the composition input");
+
+        let end_element = element.clone();
+        let listeners = vec![
+            Self::listen(&element, "compositionstart", controller, Box::new(|controller, _event| controller.start_composition())),
+            Self::listen(&element, "compositionupdate", controller, Box::new(|controller, event| {
+                controller.update_composition(&event.data().unwrap_or_default());
+            })),
+            Self::listen(&element, "compositionend", controller, Box::new(move |controller, event| {
+                controller.end_composition(&event.data().unwrap_or_default());
+                end_element.set_value("");
+            }))
+        ];
+
+        let instance = Rc::new(CompositionInput {
+            element,
+            _listeners: listeners
+        });
+        controller.borrow_mut().set_composition_hook(Some(Rc::clone(&instance) as Rc<dyn CompositionHook>));
+        instance
+    }
+
+    fn listen(element: &HtmlInputElement, event_name: &str, controller: &Rc<RefCell<EditTextRenderController>>, 
+            handler: Box<dyn Fn(&mut EditTextRenderController, &CompositionEvent)>) -> Closure<dyn FnMut(CompositionEvent)> {
+        let controller = Rc::downgrade(controller);
+        let listener = Closure::wrap(Box::new(move |event: CompositionEvent| {
+            if let Some(controller) = controller.upgrade() {
+                handler(&mut controller.borrow_mut(), &event);
+            }
+        }) as Box<dyn FnMut(CompositionEvent)>);
+        element.add_event_listener_with_callback(event_name, listener.as_ref().unchecked_ref()).expect("Should be able to listen to composition events");
+        listener
+    }
+}
+
+impl CompositionHook for CompositionInput {
+
+    fn activate(&self) {
+        self.element.focus().expect("Should be able to focus the composition input");
+    }
+
+    fn deactivate(&self) {
+        self.element.blur().expect("Should be able to blur the composition input");
+        self.element.set_value("");
+    }
+}
+
+impl Drop for CompositionInput {
+
+    fn drop(&mut self) {
+        self.element.remove();
+    }
+}
\ No newline at end of file
@@ -1,3 +1,7 @@
 mod text;
+mod area;
+mod composition;
 
-pub use text::*;
\ No newline at end of file
+pub use text::*;
+pub use area::*;
+pub use composition::*;
\ No newline at end of file
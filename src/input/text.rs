@@ -1,9 +1,12 @@
+use crate::ComponentError;
+use crate::FallibleComponent;
 use crate::behavior::*;
 
 use std::cell::RefCell;
 use std::rc::*;
 
 use wasmuri_container::*;
+use wasmuri_core::Region;
 
 pub struct TextEditField {
 
@@ -27,6 +30,70 @@ impl TextEditField {
     pub fn get_current_text(&self) -> String {
         self.render_controller.borrow().get_current_text().to_string()
     }
+
+    pub fn is_visible(&self) -> bool {
+        self.render_controller.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) -> Result<(), ComponentError> {
+        self.render_controller.borrow_mut().set_visible(visible)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.render_controller.borrow().is_enabled()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.render_controller.borrow_mut().set_enabled(enabled);
+    }
+
+    pub fn set_region(&self, new_region: Region) -> Result<(), ComponentError> {
+        self.render_controller.borrow_mut().set_region(new_region)
+    }
+
+    pub fn set_on_change(&self, on_change: Option<EditChangeAction>) {
+        self.render_controller.borrow_mut().set_on_change(on_change);
+    }
+
+    pub fn set_placeholder(&self, placeholder: Option<&str>) {
+        self.render_controller.borrow_mut().set_placeholder(placeholder);
+    }
+
+    pub fn set_scroll_mode(&self, scroll_mode: bool) {
+        self.render_controller.borrow_mut().set_scroll_mode(scroll_mode);
+    }
+
+    pub fn set_mask(&self, mask: Option<char>) {
+        self.render_controller.borrow_mut().set_mask(mask);
+    }
+
+    pub fn set_reveal_duration(&self, reveal_duration: f64) {
+        self.render_controller.borrow_mut().set_reveal_duration(reveal_duration);
+    }
+
+    pub fn set_on_submit(&self, on_submit: Option<EditSubmitAction>) {
+        self.render_controller.borrow_mut().set_on_submit(on_submit);
+    }
+}
+
+impl Focusable for TextEditField {
+
+    fn is_focused(&self) -> bool {
+        self.render_controller.borrow().is_focused()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.render_controller.borrow_mut().set_focused(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        let render_controller = self.render_controller.borrow();
+        render_controller.is_enabled() && render_controller.is_visible()
+    }
+
+    fn set_focus_handle(&mut self, handle: Option<FocusHandle>) {
+        self.render_controller.borrow_mut().set_focus_handle(handle);
+    }
 }
 
 impl Component for TextEditField {
@@ -34,4 +101,11 @@ impl Component for TextEditField {
     fn create_behaviors(&mut self) -> Vec<Rc<RefCell<dyn ComponentBehavior>>> {
         vec![Rc::clone(&self.render_behavior)]
     }
+}
+
+impl FallibleComponent for TextEditField {
+
+    fn take_attach_error(&self) -> Option<ComponentError> {
+        self.render_controller.borrow_mut().take_attach_error()
+    }
 }
\ No newline at end of file
@@ -2,10 +2,15 @@ mod passive;
 mod button;
 mod behavior;
 mod input;
+mod focus;
 mod macros;
+mod error;
+mod time;
 
 pub use passive::*;
 pub use button::*;
 pub use behavior::*;
 pub use input::*;
-pub use macros::*;
\ No newline at end of file
+pub use focus::*;
+pub use macros::*;
+pub use error::*;
\ No newline at end of file
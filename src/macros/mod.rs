@@ -1,32 +1,194 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::{ComponentError, FallibleComponent};
 use crate::behavior::*;
-use crate::button::TextButton;
-use crate::input::TextEditField;
+use crate::button::{Checkbox, RadioGroup, RadioSelectAction, TextButton, ToggleAction, ToggleButton};
+use crate::focus::FocusManager;
+use crate::input::{TextArea, TextEditField};
 
 use wasmuri_container::*;
 use wasmuri_core::*;
 use wasmuri_text::Font;
 
-pub fn add_simple_text_button<C: FnMut(&mut ComponentAgent, &mut dyn TextRenderController, &mut MouseClickParams) + 'static>
-        (layer: &mut dyn Layer, min_x: i32, min_y: i32, max_x: i32, max_y: i32, 
-        text: &str, button_color: Color, font: &Rc<Font>, alignment: TextAlignment, on_click: C) {
+#[derive(Default)]
+pub struct EditCallbacks {
+
+    pub on_change: Option<EditChangeAction>,
+    pub on_submit: Option<EditSubmitAction>
+}
+
+pub struct ToggleButtonConfig<'a> {
 
-    layer.add_component(TextButton::celled(ButtonTextRenderController::simple_tuple(text, font,
-            Box::new(AlignedTextLocation::new(Region::new(min_x, min_y, max_x, max_y), alignment, false)),
+    pub region: Region,
+    pub text: &'a str,
+    pub button_color: Color,
+    pub font: &'a Rc<Font>,
+    pub alignment: TextAlignment,
+    pub initially_checked: bool
+}
+
+// Components whose behaviors fail to attach are removed again, so they don't keep the spaces their other behaviors claimed
+pub fn try_add_component<C: FallibleComponent + 'static>(layer: &mut dyn Layer, component: Rc<RefCell<C>>) -> Result<Rc<RefCell<C>>, ComponentError> {
+    let as_component = Rc::clone(&component) as Rc<RefCell<dyn Component>>;
+    layer.add_component(Rc::clone(&as_component));
+    let attach_error = component.borrow().take_attach_error();
+    match attach_error {
+        Some(error) => {
+            layer.remove_component(&as_component);
+            Err(error)
+        },
+        None => Ok(component)
+    }
+}
+
+pub fn try_add_simple_text_button<C: FnMut(&mut ComponentAgent, &mut dyn TextRenderController, &mut ClickSource) + 'static>
+        (layer: &mut dyn Layer, region: Region, text: &str, button_color: Color, font: &Rc<Font>, alignment: TextAlignment, 
+        mut on_click: C) -> Result<Rc<RefCell<TextButton>>, ComponentError> {
+
+    let button_cell = TextButton::celled(ButtonTextRenderController::simple_tuple(text, font,
+            Box::new(AlignedTextLocation::new(region, alignment, false)),
             TextColors::create_simple_button(button_color)
-    ), Box::new(on_click)));
+    ), Box::new(move |agent, controller, source| on_click(agent, controller, source)));
+
+    try_add_component(layer, button_cell)
+}
+
+pub fn try_add_toggle_button(layer: &mut dyn Layer, config: ToggleButtonConfig, on_toggle: ToggleAction) -> Result<Rc<RefCell<ToggleButton>>, ComponentError> {
+
+    let colors = TextColors::create_simple_button(config.button_color);
+    let controller = ButtonTextRenderController::simple_celled(config.text, config.font,
+            Box::new(AlignedTextLocation::new(config.region, config.alignment, false)), colors);
+
+    // Swap the fill and background colors to show the checked state
+    controller.borrow_mut().set_checked_colors(Some(TextColors::new(colors.background_color, colors.stroke_color, colors.fill_color)));
+    let toggle_cell = ToggleButton::celled((Rc::clone(&controller) as Rc<RefCell<dyn ComponentBehavior>>, 
+            controller as Rc<RefCell<dyn TextRenderController>>), config.initially_checked, on_toggle);
+
+    try_add_component(layer, toggle_cell)
+}
+
+pub fn try_add_checkbox(layer: &mut dyn Layer, region: Region, label: &str, font: &Rc<Font>, initially_checked: bool,
+        on_toggle: ToggleAction) -> Result<Rc<RefCell<Checkbox>>, ComponentError> {
+
+    let checkbox_cell = Checkbox::celled(label, font, region, 
+            TextColors::new(Color::BLACK, Color::BLACK, Color::from_rgb(255, 255, 255)), initially_checked, on_toggle);
+
+    try_add_component(layer, checkbox_cell)
+}
+
+pub fn try_add_radio_group(layer: &mut dyn Layer, region: Region, labels: &[&str], font: &Rc<Font>, initially_selected: Option<usize>,
+        on_select: RadioSelectAction) -> Result<Rc<RefCell<RadioGroup>>, ComponentError> {
+
+    // Stack the options from top to bottom, each with an equal share of the height
+    let min_x = region.get_float_min_x();
+    let max_x = min_x + region.get_float_width();
+    let max_y = region.get_float_min_y() + region.get_float_height();
+    let option_height = region.get_float_height() / labels.len().max(1) as f32;
+    let regions = (0..labels.len()).map(|index| {
+        let option_max_y = max_y - index as f32 * option_height;
+        Region::from_floats(min_x, option_max_y - option_height, max_x, option_max_y)
+    }).collect();
+
+    let group_cell = RadioGroup::celled(labels, font, regions, 
+            TextColors::new(Color::BLACK, Color::BLACK, Color::from_rgb(255, 255, 255)), initially_selected, on_select);
+
+    try_add_component(layer, group_cell)
+}
+
+pub fn try_add_simple_edit_field(layer: &mut dyn Layer, region: Region, initial_text: &str, font: &Rc<Font>,
+        callbacks: EditCallbacks) -> Result<Rc<RefCell<TextEditField>>, ComponentError> {
+
+    let field_cell = TextEditField::celled(EditTextRenderController::simple_tuple(initial_text, font, 
+        Box::new(AlignedTextLocation::new(region, TextAlignment::LeftCenter, true)), 
+        TextColors::new(Color::BLACK, Color::BLACK, Color::from_rgb(150, 150, 150))));
+    {
+        let field = field_cell.borrow();
+        field.set_on_change(callbacks.on_change);
+        field.set_on_submit(callbacks.on_submit);
+    }
+
+    try_add_component(layer, field_cell)
 }
 
-pub fn add_simple_edit_field(layer: &mut dyn Layer, min_x: i32, min_y: i32, max_x: i32, max_y: i32, initial_text: &str, font: &Rc<Font>) 
-        -> Rc<RefCell<TextEditField>> {
+pub fn try_add_password_field(layer: &mut dyn Layer, region: Region, font: &Rc<Font>, reveal_duration: f64,
+        callbacks: EditCallbacks) -> Result<Rc<RefCell<TextEditField>>, ComponentError> {
 
-    let field_cell = TextEditField::celled(EditTextRenderController::simple_tuple(initial_text, &font, 
-        Box::new(AlignedTextLocation::new(Region::new(min_x, min_y, max_x, max_y), TextAlignment::LeftCenter, true)), 
+    let field_cell = TextEditField::celled(EditTextRenderController::simple_tuple("", font, 
+        Box::new(AlignedTextLocation::new(region, TextAlignment::LeftCenter, true)), 
         TextColors::new(Color::BLACK, Color::BLACK, Color::from_rgb(150, 150, 150))));
+    {
+        let field = field_cell.borrow();
+        field.set_mask(Some('\u{2022}'));
+        field.set_reveal_duration(reveal_duration);
+        field.set_on_change(callbacks.on_change);
+        field.set_on_submit(callbacks.on_submit);
+    }
+
+    try_add_component(layer, field_cell)
+}
+
+pub fn try_add_simple_text_area(layer: &mut dyn Layer, region: Region, line_height: f32, initial_text: &str, font: &Rc<Font>,
+        submit_on_control_enter: bool, callbacks: EditCallbacks) -> Result<Rc<RefCell<TextArea>>, ComponentError> {
+
+    let area_cell = TextArea::celled(TextAreaRenderController::simple_tuple(initial_text, font, 
+        region, line_height,
+        TextColors::new(Color::BLACK, Color::BLACK, Color::from_rgb(150, 150, 150))));
+    {
+        let area = area_cell.borrow();
+        area.set_submit_on_control_enter(submit_on_control_enter);
+        area.set_on_change(callbacks.on_change);
+        area.set_on_submit(callbacks.on_submit);
+    }
+
+    try_add_component(layer, area_cell)
+}
+
+pub fn add_simple_text_button<C: FnMut(&mut ComponentAgent, &mut dyn TextRenderController, &mut ClickSource) + 'static>
+        (layer: &mut dyn Layer, min_x: i32, min_y: i32, max_x: i32, max_y: i32, 
+        text: &str, button_color: Color, font: &Rc<Font>, alignment: TextAlignment, on_click: C) -> Rc<RefCell<TextButton>> {
+    try_add_simple_text_button(layer, Region::new(min_x, min_y, max_x, max_y), text, button_color, font, alignment, on_click).expect("Should be able to add the text button")
+}
+
+pub fn add_toggle_button(layer: &mut dyn Layer, config: ToggleButtonConfig, on_toggle: ToggleAction) -> Rc<RefCell<ToggleButton>> {
+    try_add_toggle_button(layer, config, on_toggle).expect("Should be able to add the toggle button")
+}
+
+pub fn add_checkbox(layer: &mut dyn Layer, region: Region, label: &str, font: &Rc<Font>, initially_checked: bool,
+        on_toggle: ToggleAction) -> Rc<RefCell<Checkbox>> {
+    try_add_checkbox(layer, region, label, font, initially_checked, on_toggle).expect("Should be able to add the checkbox")
+}
+
+pub fn add_radio_group(layer: &mut dyn Layer, region: Region, labels: &[&str], font: &Rc<Font>, initially_selected: Option<usize>,
+        on_select: RadioSelectAction) -> Rc<RefCell<RadioGroup>> {
+    try_add_radio_group(layer, region, labels, font, initially_selected, on_select).expect("Should be able to add the radio group")
+}
+
+pub fn add_simple_edit_field(layer: &mut dyn Layer, region: Region, initial_text: &str, font: &Rc<Font>,
+        callbacks: EditCallbacks) -> Rc<RefCell<TextEditField>> {
+    try_add_simple_edit_field(layer, region, initial_text, font, callbacks).expect("Should be able to add the edit field")
+}
+
+pub fn add_password_field(layer: &mut dyn Layer, region: Region, font: &Rc<Font>, reveal_duration: f64,
+        callbacks: EditCallbacks) -> Rc<RefCell<TextEditField>> {
+    try_add_password_field(layer, region, font, reveal_duration, callbacks).expect("Should be able to add the password field")
+}
+
+pub fn add_simple_text_area(layer: &mut dyn Layer, region: Region, line_height: f32, initial_text: &str, font: &Rc<Font>,
+        submit_on_control_enter: bool, callbacks: EditCallbacks) -> Rc<RefCell<TextArea>> {
+    try_add_simple_text_area(layer, region, line_height, initial_text, font, submit_on_control_enter, callbacks).expect("Should be able to add the text area")
+}
+
+pub fn add_focus_manager(layer: &mut dyn Layer, focusables: Vec<Rc<RefCell<dyn Focusable>>>) -> Rc<RefCell<FocusManager>> {
+    let manager_cell = FocusManager::celled();
+    {
+        let manager = manager_cell.borrow();
+        for focusable in focusables {
+            manager.add_focusable(focusable);
+        }
+    }
 
-    layer.add_component(Rc::clone(&field_cell) as Rc<RefCell<dyn Component>>);
+    layer.add_component(Rc::clone(&manager_cell) as Rc<RefCell<dyn Component>>);
 
-    field_cell
+    manager_cell
 }
\ No newline at end of file
@@ -1,9 +1,12 @@
+use crate::ComponentError;
+use crate::FallibleComponent;
 use crate::behavior::TextRenderController;
 
 use std::cell::RefCell;
 use std::rc::*;
 
 use wasmuri_container::*;
+use wasmuri_core::Region;
 
 pub struct PassiveText {
 
@@ -34,4 +37,23 @@ impl PassiveText {
     pub fn get_controller(&self) -> Rc<RefCell<dyn TextRenderController>> {
         Rc::clone(&self.render_controller)
     }
+
+    pub fn is_visible(&self) -> bool {
+        self.render_controller.borrow().is_visible()
+    }
+
+    pub fn set_visible(&self, visible: bool) -> Result<(), ComponentError> {
+        self.render_controller.borrow_mut().set_visible(visible)
+    }
+
+    pub fn set_region(&self, new_region: Region) -> Result<(), ComponentError> {
+        self.render_controller.borrow_mut().set_region(new_region)
+    }
+}
+
+impl FallibleComponent for PassiveText {
+
+    fn take_attach_error(&self) -> Option<ComponentError> {
+        self.render_controller.borrow_mut().take_attach_error()
+    }
 }
\ No newline at end of file
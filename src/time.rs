@@ -0,0 +1,3 @@
+pub(crate) fn current_time() -> f64 {
+    web_sys::window().expect("Should have a window").performance().expect("Window should have performance").now()
+}